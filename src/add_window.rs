@@ -83,15 +83,20 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
         },
         KeyCode::Enter => {
             let s = state.add.as_ref().unwrap();
-            let record = Record::new(s.company.clone(), s.jobname.clone(), s.place.clone());
-            if let Some(index) = state.add.as_ref().unwrap().modify {
-                state.rdr.0[index] = record;
+            if let Some(index) = s.modify {
+                // modify in place so we keep the dates, status and unknown fields
+                let record = &mut state.rdr.0[index];
+                record.name = s.company.clone();
+                record.subname = s.jobname.clone();
+                record.place = s.place.clone();
+                record.update_date();
             } else {
+                let record = Record::new(s.company.clone(), s.jobname.clone(), s.place.clone());
                 state.rdr.0.push(record);
             }
             state.add = None;
             state.table_state.select_last();
-            state.rdr.write().expect("Error in writing");
+            state.write().expect("Error in writing");
             state.focus = WindowFocus::Table;
         }
         KeyCode::Backspace => {
@@ -0,0 +1,115 @@
+use std::sync::{LazyLock, OnceLock};
+
+use time::{macros::time, Date, OffsetDateTime, UtcOffset};
+
+use crate::types::FORMAT;
+
+/// The local offset, `init` determines it at startup. Falls back to UTC if it is indeterminate,
+/// which happens on some platforms as soon as we have more than one thread
+static LOCAL_OFFSET: LazyLock<UtcOffset> =
+    LazyLock::new(|| UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC));
+
+/// The clock everything asks for the current time
+static CLOCK: OnceLock<Box<dyn Clock>> = OnceLock::new();
+
+/// The time tests run at, a friday
+#[cfg(test)]
+const TEST_TIME: OffsetDateTime = time::macros::datetime!(2026-10-16 12:00 UTC);
+
+/// A source of the current time
+pub(crate) trait Clock: Send + Sync {
+    /// the current time
+    fn now(&self) -> OffsetDateTime;
+}
+
+/// The clock of the system in the local offset
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc().to_offset(*LOCAL_OFFSET)
+    }
+}
+
+/// A clock that always returns the same time
+pub(crate) struct FixedClock(pub(crate) OffsetDateTime);
+
+impl FixedClock {
+    /// a clock standing at the last second of `date` in the local offset
+    pub(crate) fn end_of(date: Date) -> Self {
+        FixedClock(date.with_time(time!(23:59:59)).assume_offset(*LOCAL_OFFSET))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> OffsetDateTime {
+        self.0
+    }
+}
+
+/// determine the local offset, this has to be called before we start any thread
+pub(crate) fn init() {
+    LazyLock::force(&LOCAL_OFFSET);
+}
+
+/// use `clock` for the rest of the program, this has to be called before anything asks for the time
+pub(crate) fn set(clock: impl Clock + 'static) -> anyhow::Result<()> {
+    CLOCK
+        .set(Box::new(clock))
+        .map_err(|_| anyhow::anyhow!("The clock was already used"))
+}
+
+/// the current time
+pub(crate) fn now() -> OffsetDateTime {
+    CLOCK.get_or_init(default).now()
+}
+
+/// the clock if nobody set one
+#[cfg(not(test))]
+fn default() -> Box<dyn Clock> {
+    Box::new(SystemClock)
+}
+
+/// tests always run at the same time, unless they set a clock
+#[cfg(test)]
+fn default() -> Box<dyn Clock> {
+    Box::new(FixedClock(TEST_TIME))
+}
+
+/// the current date
+pub(crate) fn today() -> Date {
+    now().date()
+}
+
+/// the current date formatted
+pub(crate) fn today_string() -> String {
+    today().format(&FORMAT).expect("Error")
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn tests_run_on_a_fixed_day() {
+        assert_eq!(today(), date!(2026 - 10 - 16));
+        assert_eq!(now(), now());
+    }
+
+    #[test]
+    fn the_system_clock_runs_in_the_local_offset() {
+        let before = OffsetDateTime::now_utc();
+        let now = SystemClock.now();
+        assert_eq!(now.offset(), *LOCAL_OFFSET);
+        assert!(now >= before);
+    }
+
+    #[test]
+    fn end_of_is_the_last_second_of_the_day() {
+        let clock = FixedClock::end_of(date!(2026 - 09 - 01));
+        assert_eq!(clock.now().date(), date!(2026 - 09 - 01));
+        assert_eq!(clock.now().time(), time!(23:59:59));
+    }
+}
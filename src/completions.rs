@@ -0,0 +1,94 @@
+use std::{io::Write, path::Path};
+
+use clap::CommandFactory;
+use clap_complete::{
+    env::{Bash, EnvCompleter, Fish, Zsh},
+    CompleteEnv, CompletionCandidate,
+};
+
+use crate::{config, records::Records, Cli};
+
+/// The environment variable the shell sets when it asks us for completions
+const VAR: &str = "COMPLETE";
+
+/// The shells we write completions for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// answer the shell if it asks for completions and exit, otherwise do nothing
+pub(crate) fn complete() {
+    CompleteEnv::with_factory(<Cli as CommandFactory>::command)
+        .var(VAR)
+        .complete();
+}
+
+/// print the script that makes `shell` ask us for completions
+pub(crate) fn write(shell: Shell) -> anyhow::Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        Shell::Bash => &Bash,
+        Shell::Zsh => &Zsh,
+        Shell::Fish => &Fish,
+    };
+    let cmd = <Cli as CommandFactory>::command();
+    let name = cmd.get_name();
+    // the script calls us again, so it has to find this binary even if it is not in the path
+    let bin = std::env::current_exe()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| name.to_string());
+    let mut out = std::io::stdout().lock();
+    completer.write_registration(VAR, name, name, &bin, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// print the man page, or write one for every command into `dir`
+pub(crate) fn man(dir: Option<&Path>) -> anyhow::Result<()> {
+    let cmd = <Cli as CommandFactory>::command();
+    match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(cmd, dir)?
+        }
+        None => clap_mangen::Man::new(cmd).render(&mut std::io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+/// the records in the data file, none if we cannot read it, completions never fail
+fn records() -> Option<Records> {
+    config::load().ok()?;
+    Records::load().ok()
+}
+
+/// the index of every job with its company and job name
+pub(crate) fn jobs() -> Vec<CompletionCandidate> {
+    let Some(rdr) = records() else {
+        return Vec::new();
+    };
+    rdr.iter()
+        .enumerate()
+        .map(|(i, r)| {
+            CompletionCandidate::new(i.to_string())
+                .help(Some(format!("{} | {}", r.name, r.subname).into()))
+        })
+        .collect()
+}
+
+/// every company we know
+pub(crate) fn companies() -> Vec<CompletionCandidate> {
+    let Some(rdr) = records() else {
+        return Vec::new();
+    };
+    let mut names = rdr
+        .iter()
+        .map(|r| r.name.clone())
+        .filter(|n| !n.is_empty())
+        .collect::<Vec<_>>();
+    names.sort_by_key(|n| n.to_lowercase());
+    names.dedup();
+    names.into_iter().map(CompletionCandidate::new).collect()
+}
@@ -0,0 +1,293 @@
+use std::{path::PathBuf, sync::OnceLock};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{sort::SortKey, types::Status};
+
+/// The configuration, loaded once at startup
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The configuration file
+fn path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "job-data").map(|d| d.config_dir().join("config.toml"))
+}
+
+/// load the configuration file, using the defaults if it does not exist
+pub(crate) fn load() -> anyhow::Result<()> {
+    let config = match path() {
+        Some(p) if p.exists() => {
+            let s = std::fs::read_to_string(&p).context("Could not read config")?;
+            toml::from_str(&s).with_context(|| format!("Error in parsing {}", p.display()))?
+        }
+        _ => Config::default(),
+    };
+    // if it was set already somebody used the defaults before
+    let _ = CONFIG.set(config);
+    Ok(())
+}
+
+/// the configuration
+pub(crate) fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+/// The user configuration
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    /// when do we consider a job application stale
+    pub(crate) staleness: Staleness,
+    /// which statuses we use and how we move between them
+    pub(crate) workflow: Workflow,
+    /// why we get rejected
+    pub(crate) rejection: RejectionReasons,
+    /// how we compare offers
+    pub(crate) offers: Offers,
+    /// the stages of the interview pipeline
+    pub(crate) stages: Stages,
+    /// how we list jobs on the command line
+    pub(crate) list: Listing,
+}
+
+/// How we list jobs on the command line
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct Listing {
+    /// the order if no `--sort` is given, the order of the file if empty
+    pub(crate) sort: Vec<SortKey>,
+    /// reverse the default order
+    pub(crate) reverse: bool,
+}
+
+/// The stages of the interview pipeline
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Stages {
+    /// the stages in the order we go through them
+    pub(crate) template: Vec<String>,
+}
+
+impl Default for Stages {
+    fn default() -> Self {
+        Stages {
+            template: ["Screen", "Technical", "Onsite", "Final", "Offer"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// How we compare offers
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Offers {
+    /// highlight offers whose deadline is at most this many days away
+    pub(crate) deadline_days: i64,
+    /// the weights for the score
+    pub(crate) weights: OfferWeights,
+}
+
+impl Default for Offers {
+    fn default() -> Self {
+        Offers {
+            deadline_days: 3,
+            weights: OfferWeights::default(),
+        }
+    }
+}
+
+/// How much each part of an offer counts for the score
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct OfferWeights {
+    pub(crate) base_salary: f64,
+    pub(crate) bonus: f64,
+    pub(crate) equity: f64,
+}
+
+impl Default for OfferWeights {
+    fn default() -> Self {
+        OfferWeights {
+            base_salary: 1.0,
+            bonus: 0.5,
+            equity: 0.25,
+        }
+    }
+}
+
+/// The reasons we can pick from when we get rejected
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct RejectionReasons {
+    /// the reasons
+    pub(crate) reasons: Vec<String>,
+}
+
+impl Default for RejectionReasons {
+    fn default() -> Self {
+        RejectionReasons {
+            reasons: [
+                "CV screen",
+                "Take-home",
+                "Technical interview",
+                "Culture fit",
+                "Salary",
+                "Position closed",
+                "Other",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        }
+    }
+}
+
+/// When is a job application stale
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Staleness {
+    /// days after the last action for every status except todo if no rule matches
+    pub(crate) days: u32,
+    /// only count monday to friday for the default
+    pub(crate) working_days: bool,
+    /// more specific rules, the first matching one wins
+    pub(crate) rules: Vec<StalenessRule>,
+    /// propose to move long stale pending jobs to ghosted
+    pub(crate) ghosted: GhostedRule,
+}
+
+impl Default for Staleness {
+    fn default() -> Self {
+        Staleness {
+            days: 14,
+            working_days: false,
+            rules: Vec::new(),
+            ghosted: GhostedRule::default(),
+        }
+    }
+}
+
+/// A rule when a job is stale
+#[derive(Debug, Deserialize)]
+pub(crate) struct StalenessRule {
+    /// the status the rule is for, all if not given
+    pub(crate) status: Option<Status>,
+    /// the stage the rule is for, matched case insensitive as part of the stage.
+    /// An empty string only matches jobs without a stage
+    pub(crate) stage: Option<String>,
+    /// days after the last action
+    pub(crate) days: u32,
+    /// only count monday to friday
+    #[serde(default)]
+    pub(crate) working_days: bool,
+}
+
+/// When do we propose to move pending jobs to ghosted
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct GhostedRule {
+    /// do we propose it at all
+    pub(crate) enabled: bool,
+    /// days after the last action
+    pub(crate) days: u32,
+    /// only count monday to friday
+    pub(crate) working_days: bool,
+}
+
+impl Default for GhostedRule {
+    fn default() -> Self {
+        GhostedRule {
+            enabled: false,
+            days: 42,
+            working_days: false,
+        }
+    }
+}
+
+/// The statuses we use and how we move between them
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Workflow {
+    /// the statuses in the order we show them
+    pub(crate) statuses: Vec<StatusConfig>,
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Workflow {
+            statuses: Status::ALL
+                .into_iter()
+                .map(|status| StatusConfig {
+                    status,
+                    name: None,
+                    color: None,
+                    open: None,
+                    next: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The configuration of a single status, everything not given uses the defaults
+#[derive(Debug, Deserialize)]
+pub(crate) struct StatusConfig {
+    /// the status
+    pub(crate) status: Status,
+    /// the name we show
+    pub(crate) name: Option<String>,
+    /// the color we show it in
+    pub(crate) color: Option<ColorName>,
+    /// are we still in the process for jobs with this status
+    pub(crate) open: Option<bool>,
+    /// the statuses we can change to
+    pub(crate) next: Option<Vec<Status>>,
+}
+
+/// A color for the command line and the tui
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ColorName {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl ColorName {
+    /// the color in the tui
+    pub(crate) fn tui(&self) -> ratatui::style::Color {
+        use ratatui::style::Color;
+        match self {
+            ColorName::Red => Color::Red,
+            ColorName::Green => Color::Green,
+            ColorName::Yellow => Color::Yellow,
+            ColorName::Blue => Color::Blue,
+            ColorName::Magenta => Color::Magenta,
+            ColorName::Cyan => Color::Cyan,
+            ColorName::White => Color::White,
+            ColorName::Gray => Color::Gray,
+        }
+    }
+
+    /// the color on the command line
+    pub(crate) fn cli(&self) -> yansi::Color {
+        use yansi::Color;
+        match self {
+            ColorName::Red => Color::Red,
+            ColorName::Green => Color::Green,
+            ColorName::Yellow => Color::Yellow,
+            ColorName::Blue => Color::Blue,
+            ColorName::Magenta => Color::Magenta,
+            ColorName::Cyan => Color::Cyan,
+            ColorName::White => Color::White,
+            ColorName::Gray => Color::BrightBlack,
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use serde_json::Value;
+use yansi::Paint;
+
+use crate::{
+    clock,
+    exit::Outcome,
+    output::{self, Format},
+    records::{Record, Records, Timestamp},
+    types::Status,
+};
+
+/// A problem we found in a single record of the data file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Problem {
+    /// the record has no action date at all
+    NoDate,
+    /// the action dates are not in chronological order
+    UnsortedDates,
+    /// these action dates appear more than once
+    DuplicateDates(Vec<Timestamp>),
+    /// an action date lies in the future
+    FutureDate(Timestamp),
+    /// the company name is empty
+    EmptyName,
+    /// the record is an exact copy of the record with the given index
+    DuplicateRecord(usize),
+    /// the stage suggests one of the newer statuses, i.e., from a file before we had them
+    SuggestedStatus(Status),
+}
+
+impl Problem {
+    /// can we repair this problem without losing information
+    pub(crate) fn is_fixable(&self) -> bool {
+        match self {
+            Problem::NoDate
+            | Problem::UnsortedDates
+            | Problem::DuplicateDates(_)
+            | Problem::DuplicateRecord(_) => true,
+            Problem::FutureDate(_) | Problem::EmptyName | Problem::SuggestedStatus(_) => false,
+        }
+    }
+
+    /// what --fix would do about this problem of the record at `index`
+    fn fix_description(&self, index: usize) -> String {
+        match self {
+            Problem::NoDate => "sets today as the last action date".to_string(),
+            Problem::UnsortedDates => "sorts the dates".to_string(),
+            Problem::DuplicateDates(_) => "removes the duplicate dates".to_string(),
+            Problem::DuplicateRecord(_) => "removes this copy".to_string(),
+            Problem::FutureDate(_) => {
+                "please correct the date by hand (job-data open)".to_string()
+            }
+            Problem::EmptyName => "please add a company name by hand (job-data open)".to_string(),
+            Problem::SuggestedStatus(s) => format!(
+                "change it with job-data status {} {}",
+                index,
+                s.to_string().to_lowercase()
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::NoDate => f.write_str("has no last action date, this crashes most commands"),
+            Problem::UnsortedDates => f.write_str("has action dates that are not sorted"),
+            Problem::DuplicateDates(dates) => {
+                let dates = dates
+                    .iter()
+                    .map(|d| d.format(true))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "has the action dates {} more than once", dates)
+            }
+            Problem::FutureDate(d) => write!(
+                f,
+                "has the action date {} which lies in the future",
+                d.format(true)
+            ),
+            Problem::EmptyName => f.write_str("has an empty company name"),
+            Problem::DuplicateRecord(i) => write!(f, "is an exact copy of record {}", i),
+            Problem::SuggestedStatus(s) => write!(f, "has a stage that suggests the status {}", s),
+        }
+    }
+}
+
+/// A problem together with the index of the record it belongs to
+#[derive(Clone, Debug)]
+pub(crate) struct Finding {
+    pub(crate) index: usize,
+    pub(crate) problem: Problem,
+}
+
+/// older files only had pending, so we look at the stage to find a better status
+fn suggested_status(record: &Record) -> Option<Status> {
+    if record.status != Status::Pending {
+        return None;
+    }
+    let stage = record.stage.to_lowercase();
+    if stage.contains("offer") {
+        Some(Status::Offer)
+    } else if stage.contains("interview") {
+        Some(Status::Interviewing)
+    } else {
+        None
+    }
+}
+
+/// scan all records for problems
+pub(crate) fn check(rdr: &Records) -> Vec<Finding> {
+    let today = clock::today();
+    let mut findings = Vec::new();
+    for (index, record) in rdr.iter().enumerate() {
+        let mut push = |problem| findings.push(Finding { index, problem });
+        let dates = record.dates();
+        if dates.is_empty() {
+            push(Problem::NoDate);
+        }
+        if !dates.is_sorted() {
+            push(Problem::UnsortedDates);
+        }
+        let mut sorted = dates.to_vec();
+        sorted.sort_unstable();
+        let mut duplicates = sorted
+            .windows(2)
+            .filter(|w| w[0] == w[1])
+            .map(|w| w[0])
+            .collect::<Vec<_>>();
+        // one problem per record, removing them fixes all at once
+        duplicates.dedup();
+        if !duplicates.is_empty() {
+            push(Problem::DuplicateDates(duplicates));
+        }
+        if let Some(d) = dates.iter().find(|d| d.date() > today) {
+            push(Problem::FutureDate(*d));
+        }
+        if record.name.trim().is_empty() {
+            push(Problem::EmptyName);
+        }
+        if let Some(original) = rdr.iter().take(index).position(|r| r == record) {
+            push(Problem::DuplicateRecord(original));
+        }
+        if let Some(status) = suggested_status(record) {
+            push(Problem::SuggestedStatus(status));
+        }
+    }
+    findings
+}
+
+/// repair everything that is fixable, returns the number of fixed problems
+pub(crate) fn fix(rdr: &mut Records, findings: &[Finding]) -> usize {
+    let mut fixed = 0;
+    let mut remove = Vec::new();
+    for finding in findings.iter().filter(|f| f.problem.is_fixable()) {
+        let record = rdr.get_mut(finding.index).unwrap();
+        match &finding.problem {
+            Problem::NoDate => record.dates_mut().push(Timestamp::now()),
+            Problem::UnsortedDates => record.dates_mut().sort_unstable(),
+            Problem::DuplicateDates(_) => {
+                record.dates_mut().sort_unstable();
+                record.dates_mut().dedup();
+            }
+            Problem::DuplicateRecord(_) => remove.push(finding.index),
+            Problem::FutureDate(_) | Problem::EmptyName | Problem::SuggestedStatus(_) => {
+                unreachable!()
+            }
+        }
+        fixed += 1;
+    }
+    // remove from the back so the indices stay valid
+    remove.sort_unstable();
+    remove.dedup();
+    for index in remove.into_iter().rev() {
+        rdr.0.remove(index);
+    }
+    fixed
+}
+
+/// The fields of a finding in the other formats
+const FIELDS: [&str; 6] = ["Index", "Name", "Subname", "Problem", "Fixable", "Fix"];
+
+/// write all findings in a machine readable format
+fn write(rdr: &Records, findings: &[Finding], format: Format) -> anyhow::Result<()> {
+    let maps = findings
+        .iter()
+        .map(|f| {
+            let record = rdr.get(f.index).unwrap();
+            output::row(
+                FIELDS,
+                [
+                    Value::from(f.index),
+                    Value::from(record.name.as_str()),
+                    Value::from(record.subname.as_str()),
+                    Value::from(f.problem.to_string()),
+                    Value::from(f.problem.is_fixable()),
+                    Value::from(f.problem.fix_description(f.index)),
+                ],
+            )
+        })
+        .collect::<Vec<_>>();
+    output::write_maps(format, &FIELDS, &maps)
+}
+
+/// print all findings
+pub(crate) fn print(rdr: &Records, findings: &[Finding]) {
+    for finding in findings {
+        let record = rdr.get(finding.index).unwrap();
+        let hint = if finding.problem.is_fixable() {
+            format!("--fix {}", finding.problem.fix_description(finding.index))
+                .green()
+                .to_string()
+        } else {
+            finding
+                .problem
+                .fix_description(finding.index)
+                .yellow()
+                .to_string()
+        };
+        println!(
+            "{:2} | {} | {}: {} ({})",
+            finding.index,
+            record.name.bold(),
+            record.subname.bold(),
+            finding.problem,
+            hint
+        );
+    }
+}
+
+/// run the doctor, repairing what we can if `fix` is set, otherwise the findings are
+/// written in `format`
+pub(crate) fn run(
+    rdr: &mut Records,
+    fix_problems: bool,
+    format: Format,
+) -> anyhow::Result<Outcome> {
+    let findings = check(rdr);
+    if format != Format::Table {
+        write(rdr, &findings, format)?;
+        return Ok(Outcome::Done);
+    }
+    if findings.is_empty() {
+        println!("No problems found in {} records", rdr.len());
+        return Ok(if fix_problems {
+            Outcome::Unchanged
+        } else {
+            Outcome::Done
+        });
+    }
+    print(rdr, &findings);
+    let fixable = findings.iter().filter(|f| f.problem.is_fixable()).count();
+    if fix_problems {
+        let fixed = fix(rdr, &findings);
+        rdr.write()?;
+        println!("Fixed {} of {} problems", fixed, findings.len());
+        if fixed == 0 {
+            return Ok(Outcome::Unchanged);
+        }
+    } else {
+        println!(
+            "Found {} problems, {} can be fixed with `job-data doctor --fix`",
+            findings.len(),
+            fixable
+        );
+    }
+    Ok(Outcome::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the problems of every record
+    fn problems(rdr: &Records) -> Vec<(usize, Problem)> {
+        check(rdr)
+            .into_iter()
+            .map(|f| (f.index, f.problem))
+            .collect()
+    }
+
+    #[test]
+    fn check_finds_broken_dates() {
+        let rdr = Records::from(vec![
+            Record::test("Acme", &[]),
+            Record::test(
+                "Bar",
+                &["05-09-2026", "01-09-2026", "05-09-2026", "01-09-2026"],
+            ),
+            Record::test("Baz", &["01-09-2026", "01-09-2027"]),
+        ]);
+        let dates = |ds: [&str; 2]| {
+            ds.map(|d| serde_json::from_value(serde_json::json!(d)).unwrap())
+                .to_vec()
+        };
+        assert_eq!(
+            problems(&rdr),
+            [
+                (0, Problem::NoDate),
+                (1, Problem::UnsortedDates),
+                // one problem for all duplicates of the record
+                (
+                    1,
+                    Problem::DuplicateDates(dates(["01-09-2026", "05-09-2026"]))
+                ),
+                (2, Problem::FutureDate(rdr.get(2).unwrap().last_action())),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_finds_broken_records() {
+        let rdr = Records::from(vec![
+            Record::test("", &["01-09-2026"]),
+            Record::test("Acme", &["01-09-2026"]).with_stage("second interview"),
+            Record::test("Acme", &["01-09-2026"]).with_stage("second interview"),
+        ]);
+        assert_eq!(
+            problems(&rdr),
+            [
+                (0, Problem::EmptyName),
+                (1, Problem::SuggestedStatus(Status::Interviewing)),
+                (2, Problem::DuplicateRecord(1)),
+                (2, Problem::SuggestedStatus(Status::Interviewing)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fix_repairs_what_it_can() {
+        let mut rdr = Records::from(vec![
+            Record::test("Acme", &[]),
+            Record::test(
+                "Bar",
+                &["05-09-2026", "01-09-2026", "05-09-2026", "01-09-2026"],
+            ),
+            Record::test(
+                "Bar",
+                &["05-09-2026", "01-09-2026", "05-09-2026", "01-09-2026"],
+            ),
+            Record::test("", &["01-09-2026"]),
+        ]);
+        let findings = check(&rdr);
+        // the copy has the same date problems, the empty name stays
+        assert_eq!(findings.len(), 7);
+        assert_eq!(fix(&mut rdr, &findings), 6);
+        assert_eq!(rdr.len(), 3);
+        assert_eq!(rdr.get(0).unwrap().get_date(), clock::today());
+        let dates = rdr
+            .get(1)
+            .unwrap()
+            .dates()
+            .iter()
+            .map(|d| d.format(false))
+            .collect::<Vec<_>>();
+        assert_eq!(dates, ["01-09-2026", "05-09-2026"]);
+        assert_eq!(problems(&rdr), [(2, Problem::EmptyName)]);
+    }
+}
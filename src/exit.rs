@@ -0,0 +1,94 @@
+use std::process::ExitCode;
+
+/// How a command ended, scripts can tell it from the exit code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    /// the command changed the data or only showed it
+    Done,
+    /// the command did not change anything, i.e., because it was not confirmed
+    Unchanged,
+}
+
+/// any other error
+const ERROR: u8 = 1;
+/// the input does not make sense, clap uses the same code for wrong arguments
+const INVALID: u8 = 2;
+/// there is no record at the index
+const NOT_FOUND: u8 = 3;
+/// nothing changed
+const UNCHANGED: u8 = 4;
+
+impl From<Outcome> for ExitCode {
+    fn from(value: Outcome) -> Self {
+        match value {
+            Outcome::Done => ExitCode::SUCCESS,
+            Outcome::Unchanged => ExitCode::from(UNCHANGED),
+        }
+    }
+}
+
+/// There is no record at the index
+#[derive(Debug)]
+pub(crate) struct NotFound(pub(crate) usize);
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Could not find record {}", self.0)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// The input does not make sense
+#[derive(Debug)]
+pub(crate) struct Invalid(pub(crate) String);
+
+impl std::fmt::Display for Invalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Invalid {}
+
+/// an error for input that does not make sense
+pub(crate) fn invalid(msg: impl Into<String>) -> anyhow::Error {
+    Invalid(msg.into()).into()
+}
+
+/// the exit code for a command that failed with `e`
+pub(crate) fn code(e: &anyhow::Error) -> ExitCode {
+    if e.is::<NotFound>() {
+        ExitCode::from(NOT_FOUND)
+    } else if e.is::<Invalid>() {
+        ExitCode::from(INVALID)
+    } else {
+        ExitCode::from(ERROR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+
+    #[test]
+    fn errors_have_their_own_codes() {
+        assert_eq!(code(&NotFound(3).into()), ExitCode::from(NOT_FOUND));
+        assert_eq!(code(&invalid("no index given")), ExitCode::from(INVALID));
+        assert_eq!(code(&anyhow::anyhow!("disk full")), ExitCode::from(ERROR));
+        // the context does not hide the error
+        let e = Err::<(), _>(NotFound(3)).context("Could not change the status");
+        assert_eq!(code(&e.unwrap_err()), ExitCode::from(NOT_FOUND));
+    }
+
+    #[test]
+    fn nothing_changed_is_not_a_success() {
+        assert_eq!(ExitCode::from(Outcome::Done), ExitCode::SUCCESS);
+        assert_eq!(
+            ExitCode::from(Outcome::Unchanged),
+            ExitCode::from(UNCHANGED)
+        );
+    }
+}
@@ -7,19 +7,22 @@ use ratatui::{
     prelude::*,
     widgets::TableState,
 };
-use std::{collections::HashSet, io::stdout, ops::ControlFlow};
+use std::{collections::HashSet, io::stdout, ops::ControlFlow, time::Instant};
 
 use crate::{
-    add_window, help_window, info_window,
+    add_window, help_window, info_window, offers_window,
+    query::{Query, SearchMode},
     records::Records,
-    searchbar, status_edit_window, summarybar, table_window,
+    rejection_window, reload_window, searchbar, status_edit_window, status_pick_window, summarybar, table_window,
+    transition_window,
     types::{GuiState, GuiView, Save, WindowFocus},
 };
 
 /// main gui run function
-pub(crate) fn run(rdr: &mut Records) -> anyhow::Result<Save> {
+pub(crate) fn run(rdr: &mut Records, show_times: bool) -> anyhow::Result<Save> {
     rdr.0.sort_unstable();
     //rdr.reverse();
+    let disk_records = rdr.0.clone();
 
     stdout().execute(EnterAlternateScreen)?;
     enable_raw_mode()?;
@@ -33,7 +36,14 @@ pub(crate) fn run(rdr: &mut Records) -> anyhow::Result<Save> {
         focus: WindowFocus::Table,
         changed_this_exection: HashSet::new(),
         search: None,
+        search_mode: SearchMode::Plain,
+        query: Query::default(),
+        query_error: None,
         add: None,
+        show_times,
+        disk_records,
+        disk_modified: Records::modified(),
+        last_disk_check: Instant::now(),
     };
 
     let save;
@@ -53,11 +63,20 @@ pub(crate) fn run(rdr: &mut Records) -> anyhow::Result<Save> {
             searchbar::draw(frame, layout[2], &state);
             match &state.focus {
                 WindowFocus::Table => {}
-                WindowFocus::StageEdit(_, _) => status_edit_window::draw(frame, layout[1], &state),
+                WindowFocus::StageEdit(_, _, _) => status_edit_window::draw(frame, layout[1], &state),
+                WindowFocus::StatusPick(_, _, _) => {
+                    status_pick_window::draw(frame, layout[1], &state)
+                }
+                WindowFocus::Rejection(_, _, _) => rejection_window::draw(frame, layout[1], &state),
+                WindowFocus::TransitionComment(_, _) => {
+                    transition_window::draw(frame, layout[1], &state)
+                }
                 WindowFocus::Help => help_window::draw(frame, layout[1], &state),
                 WindowFocus::Search => {}
                 WindowFocus::Add => add_window::draw(frame, layout[1], &state),
                 WindowFocus::Info => info_window::draw(frame, layout[1], &state),
+                WindowFocus::Offers => offers_window::draw(frame, layout[1], &state),
+                WindowFocus::Reload => reload_window::draw(frame, layout[1], &state),
             };
         })?;
         if event::poll(std::time::Duration::from_millis(16))? {
@@ -72,17 +91,29 @@ pub(crate) fn run(rdr: &mut Records) -> anyhow::Result<Save> {
                                 break;
                             }
                         }
-                        WindowFocus::StageEdit(_, _) => {
+                        WindowFocus::StageEdit(_, _, _) => {
                             status_edit_window::handle_input(key, &mut state);
                         }
+                        WindowFocus::StatusPick(_, _, _) => {
+                            status_pick_window::handle_input(key, &mut state);
+                        }
+                        WindowFocus::Rejection(_, _, _) => {
+                            rejection_window::handle_input(key, &mut state);
+                        }
+                        WindowFocus::TransitionComment(_, _) => {
+                            transition_window::handle_input(key, &mut state);
+                        }
                         WindowFocus::Help => help_window::handle_input(key, &mut state),
                         WindowFocus::Search => searchbar::handle_input(key, &mut state),
                         WindowFocus::Add => add_window::handle_input(key, &mut state),
                         WindowFocus::Info => info_window::handle_input(key, &mut state),
+                        WindowFocus::Offers => offers_window::handle_input(key, &mut state),
+                        WindowFocus::Reload => reload_window::handle_input(key, &mut state),
                     };
                 }
             }
         }
+        reload_window::check(&mut state);
     }
 
     stdout().execute(LeaveAlternateScreen)?;
@@ -29,14 +29,17 @@ pub(crate) fn draw(frame: &mut Frame, _: Rect, _: &GuiState) {
     let help = (List::new([
         styled_text("Esc", "to exit without saving"),
         styled_text("q", "to exit wit saving"),
-        styled_text("Enter", "to toggle through status"),
+        styled_text("Enter", "to pick the next status"),
         styled_text("Delete", "delete an entry"),
         styled_text("v", "toggle which entries we see"),
-        styled_text("s", "to change stage"),
+        styled_text("t", "toggle showing the time of the last action"),
+        styled_text("s", "to move to the next stage or type one"),
+        styled_text("p/f", "the current stage passed/failed"),
         styled_text("?", "help"),
-        styled_text("/", "search the names"),
+        styled_text("/", "search, Tab toggles plain/regex/fuzzy"),
         styled_text("a", "add a job"),
         styled_text("i", "information about highlighted job"),
+        styled_text("o", "compare the open offers"),
         styled_text("e", "edit the entry"),
     ]))
     .block(Block::new().borders(Borders::ALL));
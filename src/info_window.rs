@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::types::{center, GuiState, WindowFocus};
+use crate::types::{center, GuiState, WindowFocus, FORMAT};
 
 /// draw the info frame
 pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
@@ -18,15 +18,22 @@ pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
     let index = state.get_real_index();
     let record = state.rdr.get(index).unwrap();
 
-    let text = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(vec![Span::from("Name: "), Span::from(record.name.clone())]),
         Line::from(vec![
             Span::from("Subname: "),
             Span::from(record.subname.clone()),
         ]),
         Line::from(vec![
-            Span::from("Date: "),
-            Span::from(record.date_string()),
+            Span::from("Dates: "),
+            Span::from(
+                record
+                    .dates()
+                    .iter()
+                    .map(|d| d.format(state.show_times))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
         ]),
         Line::from(vec![
             Span::from("Stage: "),
@@ -38,14 +45,50 @@ pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
         ]),
         Line::from(vec![
             Span::from("Status: "),
-            Span::from(record.status.to_string()),
+            Span::from(record.status.name()),
         ]),
         Line::from(vec![
             Span::from("Place: "),
             Span::from(record.place.clone()),
         ]),
-    ])
-    .block(Block::bordered().title("Info"));
+    ];
+    for t in &record.transitions {
+        let mut spans = vec![
+            Span::from("Changed: "),
+            Span::from(t.date.format(state.show_times)),
+            Span::from(format!(" {} -> {}", t.from.name(), t.to.name())),
+        ];
+        if !t.comment.is_empty() {
+            spans.push(Span::from(format!(": {}", t.comment)));
+        }
+        lines.push(Line::from(spans));
+    }
+    if !record.stages.is_empty() {
+        lines.push(Line::from(vec![
+            Span::from("Stages: "),
+            Span::from(
+                record
+                    .stages
+                    .iter()
+                    .map(|s| format!("{} {} ({})", s.date.format(&FORMAT).unwrap(), s, s.result))
+                    .collect::<Vec<String>>()
+                    .join(" -> "),
+            ),
+        ]));
+    }
+    if let Some(rejection) = &record.rejection {
+        lines.push(Line::from(vec![
+            Span::from("Rejected: "),
+            Span::from(rejection.to_string()),
+        ]));
+    }
+    if let Some(offer) = &record.offer {
+        lines.push(Line::from(vec![
+            Span::from("Offer: "),
+            Span::from(offer.to_string()),
+        ]));
+    }
+    let text = Paragraph::new(lines).block(Block::bordered().title("Info"));
     frame.render_widget(Clear, area);
     frame.render_widget(text, area);
 }
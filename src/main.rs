@@ -1,25 +1,59 @@
 use anyhow::Context;
-use clap::{arg, command, Parser};
-use inquire::Confirm;
-use records::{Record, Records};
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use clap_complete::ArgValueCandidates;
+use inquire::{Select, Text};
+use records::{Record, Records, StageResult, Timestamp};
 use std::{
     collections::HashMap,
     path::PathBuf,
+    process::ExitCode,
     sync::LazyLock,
 };
-use types::{Save, Status};
+use clock::FixedClock;
+use exit::Outcome;
+use output::Format;
+use query::{Query, SearchMode};
+use selection::Selection;
+use sort::SortKey;
+use table::Column;
+use time::{macros::format_description, Date};
+use types::{Save, Status, FORMAT};
+use wizard::Answers;
 use yansi::Paint;
 
 mod add_window;
+mod clock;
+mod completions;
+mod config;
+mod doctor;
+mod exit;
 mod gui;
 mod help_window;
 mod info_window;
+mod merge;
+mod offers;
+mod offers_window;
+mod output;
+mod prompt;
+mod query;
 mod records;
+mod rejection_window;
+mod reload_window;
+mod report;
 mod searchbar;
+mod selection;
+mod sort;
+mod stages;
+mod staleness;
 mod status_edit_window;
+mod status_pick_window;
 mod summarybar;
+mod table;
 mod table_window;
+mod transition_window;
 mod types;
+mod wizard;
+mod workflow;
 
 static PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     let u = directories::UserDirs::new().expect("Cannot find userdirs");
@@ -28,92 +62,735 @@ static PATH: LazyLock<PathBuf> = LazyLock::new(|| {
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about)]
+#[command(group(ArgGroup::new("legacy").multiple(false)))]
 struct Cli {
-    /// show all values
-    #[arg(long)]
+    /// deprecated, use `list --all`
+    #[arg(long, hide = true, conflicts_with = "legacy")]
     all: bool,
 
-    /// change the status to pending of input
-    #[arg(short, long, value_name = "index")]
-    pending: Option<usize>,
+    /// deprecated, use `status <index> pending`
+    #[arg(short, long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    pending: Option<Option<usize>>,
 
-    /// change the status to rejected of input
-    #[arg(short, long, value_name = "index")]
-    rejected: Option<usize>,
+    /// deprecated, use `status <index> rejected`
+    #[arg(short, long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    rejected: Option<Option<usize>>,
 
-    /// set to todo
-    #[arg(short, long, value_name = "index")]
-    todo: Option<usize>,
+    /// deprecated, use `status <index> todo`
+    #[arg(short, long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    todo: Option<Option<usize>>,
 
-    /// show full entry for one
-    #[arg(short, long, value_name = "index")]
-    info: Option<usize>,
+    /// deprecated, use `status <index> applied`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    applied: Option<Option<usize>>,
 
-    /// open the file in editor
-    #[arg(short, long)]
+    /// deprecated, use `status <index> interviewing`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    interviewing: Option<Option<usize>>,
+
+    /// deprecated, use `status <index> offer`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    offer: Option<Option<usize>>,
+
+    /// deprecated, use `status <index> accepted`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    accepted: Option<Option<usize>>,
+
+    /// deprecated, use `status <index> declined`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    declined: Option<Option<usize>>,
+
+    /// deprecated, use `status <index> withdrawn`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    withdrawn: Option<Option<usize>>,
+
+    /// deprecated, use `status <index> ghosted`
+    #[arg(long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    ghosted: Option<Option<usize>>,
+
+    /// deprecated, use `info <index>`
+    #[arg(short, long, value_name = "index", num_args = 0..=1, hide = true, group = "legacy")]
+    info: Option<Option<usize>>,
+
+    /// deprecated, use `open`
+    #[arg(short, long, hide = true, group = "legacy")]
     open: bool,
 
-    /// Info to change
-    #[arg(long, num_args=2, value_names = ["index", "Info"])]
+    /// deprecated, use `edit <index> --info <Info>`
+    #[arg(long, num_args=2, value_names = ["index", "Info"], hide = true, group = "legacy")]
     info_change: Option<Vec<String>>,
 
-    /// stage  to change
-    #[arg(long, num_args=2, value_names = ["index", "Stage"])]
+    /// deprecated, use `stage set <index> <Stage>`
+    #[arg(long, num_args=2, value_names = ["index", "Stage"], hide = true, group = "legacy")]
     stage_change: Option<Vec<String>>,
 
-    /// add new job status
-    #[arg(short, long, num_args = 2..=3, value_names = ["Company Name", "Sub Name", "Additional Info"])]
+    /// deprecated, use `add <Company Name> <Sub Name>`
+    #[arg(short, long, num_args = 2..=3, value_names = ["Company Name", "Sub Name", "Additional Info"], hide = true, group = "legacy")]
     add: Option<Vec<String>>,
 
-    /// search for a company
-    #[arg(short, long)]
+    /// deprecated, use `search <name>`
+    #[arg(short, long, hide = true, group = "legacy", add = ArgValueCandidates::new(completions::companies))]
     search: Option<String>,
 
+    /// deprecated, use `tui`
+    #[arg(long, hide = true, group = "legacy")]
+    tui: bool,
+
+    /// change the status even if the workflow does not allow it
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// show the time of the last action and not only the date
+    #[arg(long, global = true)]
+    times: bool,
+
+    /// answer every question with yes, needed to change data without a terminal
+    #[arg(short, long, global = true, visible_alias = "no-confirm")]
+    yes: bool,
+
+    /// show the data as it was at the end of this date (dd-mm-yyyy or yyyy-mm-dd)
+    #[arg(long, global = true, value_name = "date", value_parser = parse_date)]
+    as_of: Option<Date>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// show the open jobs, this is the default
+    List {
+        /// show the closed jobs as well, implied by a status filter
+        #[arg(long)]
+        all: bool,
+        #[command(flatten)]
+        filters: Filters,
+        #[command(flatten)]
+        order: Order,
+        /// how to print the jobs
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// the columns to show, i.e., `name,status,date`, the table fits them to the terminal
+        /// and the other formats only write their fields and the index
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+    },
+    /// add a new job
+    Add {
+        #[command(flatten)]
+        job: NewJob,
+    },
+    /// change the details of jobs
+    Edit {
+        /// the jobs, indices like 3-7,12, a name or a query like "status:pending stale:yes",
+        /// we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        jobs: Option<Selection>,
+        /// the company
+        #[arg(long)]
+        name: Option<String>,
+        /// the job name
+        #[arg(long)]
+        subname: Option<String>,
+        /// where the job is
+        #[arg(long)]
+        place: Option<String>,
+        /// some additional information
+        #[arg(long)]
+        info: Option<String>,
+        /// ask for everything one after the other, for a single job
+        #[arg(short, long)]
+        interactive: bool,
+    },
+    /// change the status of jobs
+    #[command(allow_missing_positional = true)]
+    Status {
+        /// the jobs, indices like 3-7,12, a name or a query like "status:pending stale:yes",
+        /// we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        jobs: Option<Selection>,
+        /// the new status
+        status: Status,
+    },
+    /// move through the stages of the interview pipeline
+    Stage {
+        #[command(subcommand)]
+        command: StageCommand,
+    },
+    /// show everything we know about a job
+    Info {
+        /// the job, an index or a name, we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        job: Option<Selection>,
+        /// how to print the job
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// search all jobs
+    Search {
+        #[command(flatten)]
+        filters: Filters,
+        #[command(flatten)]
+        order: Order,
+        /// how to print the jobs
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+        /// the columns to show, i.e., `name,status,date`, the table fits them to the terminal
+        /// and the other formats only write their fields and the index
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<Column>,
+    },
     /// open the tui
+    Tui,
+    /// open the data file in the editor
+    Open,
+    /// check the data file for problems
+    Doctor {
+        /// repair the problems that can be repaired safely
+        #[arg(long)]
+        fix: bool,
+        /// how to print the problems
+        #[arg(long, value_enum, default_value_t, conflicts_with = "fix")]
+        format: Format,
+    },
+    /// merge two diverging data files, i.e., a conflicted copy of a file sync service
+    Merge {
+        /// our data file
+        ours: PathBuf,
+        /// the data file to merge into ours
+        theirs: PathBuf,
+        /// the common ancestor of both files, if we have one
+        #[arg(long)]
+        base: Option<PathBuf>,
+        /// where to write the merged data, defaults to our file
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// show reports about the job hunt
+    Report {
+        #[command(subcommand)]
+        report: Report,
+    },
+    /// manage the offers we got
+    Offers {
+        #[command(subcommand)]
+        command: OffersCommand,
+    },
+    /// show pending applications that look ghosted according to the config
+    Ghosted {
+        /// move them to ghosted
+        #[arg(long)]
+        apply: bool,
+        /// how to print the jobs
+        #[arg(long, value_enum, default_value_t, conflicts_with = "apply")]
+        format: Format,
+    },
+    /// print the shell completions, i.e., `source <(job-data completions bash)` in your .bashrc
+    Completions {
+        /// the shell
+        #[arg(value_enum)]
+        shell: completions::Shell,
+    },
+    /// print the man page
+    Man {
+        /// write a page for every command into this directory instead
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+/// Everything we know about a new job
+#[derive(Args, Debug)]
+struct NewJob {
+    /// the company
+    #[arg(required_unless_present = "interactive")]
+    company: Option<String>,
+    /// the job
+    #[arg(required_unless_present = "interactive")]
+    job: Option<String>,
+    /// where the job is
     #[arg(long)]
-    tui: bool,
+    place: Option<String>,
+    /// some additional information
+    #[arg(long)]
+    info: Option<String>,
+    /// the stage shown in the list
+    #[arg(long)]
+    stage: Option<String>,
+    /// the status to start with
+    #[arg(long, value_enum, default_value_t = Status::Todo)]
+    status: Status,
+    /// when it happened if not today, i.e., we applied last week (dd-mm-yyyy or yyyy-mm-dd)
+    #[arg(long, value_name = "date", value_parser = parse_date)]
+    date: Option<Date>,
+    /// ask for everything one after the other, starting with what is given
+    #[arg(short, long)]
+    interactive: bool,
 }
 
-/// print all entries
-fn print(rdr: &Records, truncate: bool, show_all: bool) -> anyhow::Result<()> {
-    print_stats(rdr)?;
-    if truncate {
-        println!(
-            "{:2} | {:^10} | {:^20} | {:^20} | {:^37} | {:^30} | {}",
-            "",
-            "Status".underline(),
-            "Last Date".underline(),
-            "Name".underline(),
-            "Subname".underline(),
-            "Stage".underline(),
-            "Place".underline(),
+impl NewJob {
+    /// ask for everything in the wizard, `None` if the user gives up
+    fn ask(self, rdr: &Records) -> anyhow::Result<Option<NewJob>> {
+        let current = Answers {
+            name: self.company.unwrap_or_default(),
+            subname: self.job.unwrap_or_default(),
+            place: self.place.unwrap_or_default(),
+            info: self.info.unwrap_or_default(),
+            stage: self.stage.unwrap_or_default(),
+            status: self.status,
+            date: Some(self.date.unwrap_or_else(clock::today)),
+        };
+        let Some(answers) = wizard::ask(rdr, current, &workflow::statuses())? else {
+            return Ok(None);
+        };
+        if !wizard::confirm(&answers, "Do you want to add this job")? {
+            return Ok(None);
+        }
+        Ok(Some(NewJob {
+            company: Some(answers.name),
+            job: Some(answers.subname),
+            place: Some(answers.place),
+            info: Some(answers.info),
+            stage: Some(answers.stage),
+            status: answers.status,
+            // today we know the time as well
+            date: answers.date.filter(|d| *d != clock::today()),
+            interactive: false,
+        }))
+    }
+
+    /// the record for the job, an error if it does not make sense
+    fn record(self) -> anyhow::Result<Record> {
+        let (company, job) = (
+            self.company.unwrap_or_default(),
+            self.job.unwrap_or_default(),
         );
-    } else {
-        println!(
-            "{:2} | {:^10} | {:^20} | {:^20} | {:^37} | {:^30} | {:^20} | {}",
-            "",
-            "Status".underline(),
-            "Last Date".underline(),
-            "Name".underline(),
-            "Subname".underline(),
-            "Stage".underline(),
-            "Info".underline(),
-            "Place".underline(),
+        if company.trim().is_empty() && job.trim().is_empty() {
+            return Err(exit::invalid(
+                "The company and the job cannot both be empty",
+            ));
+        }
+        if self.date.is_some_and(|d| d > clock::today()) {
+            return Err(exit::invalid("The date cannot be in the future"));
+        }
+        let mut record = Record::new(company, job, self.place.unwrap_or_default());
+        record.additional_info = self.info.unwrap_or_default();
+        record.stage = self.stage.unwrap_or_default();
+        record.status = self.status;
+        if let Some(date) = self.date {
+            *record.dates_mut() = vec![Timestamp::Date(date)];
+        }
+        Ok(record)
+    }
+}
+
+/// Filters for the jobs we show, all of them have to match
+#[derive(Args, Debug, Default)]
+struct Filters {
+    /// only jobs with one of these statuses
+    #[arg(long, value_enum, value_delimiter = ',')]
+    status: Vec<Status>,
+    /// only jobs with the last action on or after the date
+    #[arg(long, value_name = "date", value_parser = parse_date)]
+    since: Option<Date>,
+    /// only jobs with the last action on or before the date
+    #[arg(long, value_name = "date", value_parser = parse_date)]
+    until: Option<Date>,
+    /// only jobs whose place contains the text
+    #[arg(long)]
+    place: Option<String>,
+    /// only jobs whose stage contains the text
+    #[arg(long)]
+    stage: Option<String>,
+    /// only open jobs that are stale
+    #[arg(long)]
+    stale: bool,
+    /// match the words as regular expressions
+    #[arg(long, conflicts_with = "fuzzy")]
+    regex: bool,
+    /// match the words fuzzy, the best matches come first
+    #[arg(long)]
+    fuzzy: bool,
+    /// a query like `status:pending place:berlin since:2026-09-01 "backend"`,
+    /// words without a filter search all text fields ignoring case
+    #[arg(add = ArgValueCandidates::new(completions::companies))]
+    query: Vec<String>,
+}
+
+impl Filters {
+    /// the options and the query together
+    fn query(&self) -> anyhow::Result<Query> {
+        use query::Filter;
+        let mut filters = Vec::new();
+        if !self.status.is_empty() {
+            filters.push(Filter::Status(self.status.clone()));
+        }
+        filters.extend(self.since.map(Filter::Since));
+        filters.extend(self.until.map(Filter::Until));
+        filters.extend(self.place.clone().map(Filter::Place));
+        filters.extend(self.stage.clone().map(Filter::Stage));
+        if self.stale {
+            filters.push(Filter::Stale(true));
+        }
+        let mode = if self.regex {
+            SearchMode::Regex
+        } else if self.fuzzy {
+            SearchMode::Fuzzy
+        } else {
+            SearchMode::Plain
+        };
+        // the shell already split the words, an argument with a filter is a query of its own
+        // and any other one is a phrase, i.e., "backend dev"
+        let mut query = Query(filters);
+        for arg in &self.query {
+            let part = if query::has_filter(arg) {
+                Query::parse(arg, mode)
+            } else {
+                Query::text(arg.trim_matches('"'), mode)
+            };
+            query = query.and(part.map_err(exit::invalid)?);
+        }
+        Ok(query)
+    }
+}
+
+/// The order we show the jobs in
+#[derive(Args, Debug, Default)]
+struct Order {
+    /// sort by these keys, the first one counts most, defaults to the config
+    #[arg(long, value_enum, value_delimiter = ',')]
+    sort: Vec<SortKey>,
+    /// reverse the order
+    #[arg(long)]
+    reverse: bool,
+}
+
+impl Order {
+    /// sort the rows, without keys we rank searches for text by how well they match
+    /// and use the default from the config otherwise
+    fn sort(&self, rows: &mut [(usize, &Record)], query: &Query) {
+        let list = &config::get().list;
+        if !self.sort.is_empty() {
+            sort::sort(rows, &self.sort, self.reverse);
+        } else if query.has_text() {
+            sort::sort(rows, &list.sort, list.reverse);
+            // the sort is stable, so equally good matches keep the default order
+            rows.sort_by_key(|(_, r)| std::cmp::Reverse(query.score(r)));
+            if self.reverse {
+                rows.reverse();
+            }
+        } else {
+            sort::sort(rows, &list.sort, list.reverse != self.reverse);
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Report {
+    /// break down the rejections by reason and by stage
+    Rejections {
+        /// how to print the report, the other formats list every rejection
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// show how many applications reach each stage
+    Funnel {
+        /// how to print the report
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum StageCommand {
+    /// start the next stage of jobs
+    #[command(allow_missing_positional = true)]
+    Add {
+        /// the jobs, indices like 3-7,12, a name or a query like "status:pending stale:yes",
+        /// we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        jobs: Option<Selection>,
+        /// the stage out of the configured template
+        name: String,
+        /// anything to add, i.e., "with the CTO"
+        #[arg(long, default_value = "")]
+        label: String,
+    },
+    /// set how the current stage of jobs went
+    #[command(allow_missing_positional = true)]
+    Result {
+        /// the jobs, indices like 3-7,12, a name or a query like "status:pending stale:yes",
+        /// we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        jobs: Option<Selection>,
+        /// how it went
+        result: StageResult,
+    },
+    /// set the stage shown in the list to any text
+    #[command(allow_missing_positional = true)]
+    Set {
+        /// the jobs, indices like 3-7,12, a name or a query like "status:pending stale:yes",
+        /// we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        jobs: Option<Selection>,
+        /// the text
+        stage: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum OffersCommand {
+    /// compare all open offers side by side
+    Compare {
+        /// score the offers with the weights from the config
+        #[arg(long)]
+        score: bool,
+        /// how to print the offers
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// set the details of the offer for a job, everything not given stays as it is
+    Set {
+        /// the job with the offer, an index or a name, we let you pick one if not given
+        #[arg(value_parser = selection::parse, add = ArgValueCandidates::new(completions::jobs))]
+        job: Option<Selection>,
+        /// the yearly base salary
+        #[arg(long)]
+        base: Option<u64>,
+        /// the yearly bonus
+        #[arg(long)]
+        bonus: Option<u64>,
+        /// the yearly value of the equity
+        #[arg(long)]
+        equity: Option<u64>,
+        /// everything else, i.e., vacation days
+        #[arg(long)]
+        benefits: Option<String>,
+        /// when we would start
+        #[arg(long, value_name = "date", value_parser = parse_date)]
+        start: Option<Date>,
+        /// until when we have to decide
+        #[arg(long, value_name = "date", value_parser = parse_date)]
+        deadline: Option<Date>,
+    },
+}
+
+/// parse the index of a deprecated flag that takes the index as text
+fn parse_index(v: &[String]) -> anyhow::Result<usize> {
+    v.first()
+        .unwrap()
+        .parse::<usize>()
+        .map_err(|_| exit::invalid("Not a valid integer"))
+}
+
+impl Cli {
+    /// the status change of the deprecated flags, if any, the index may be missing
+    fn status_change(&self) -> Option<(Option<usize>, Status)> {
+        [
+            (self.pending, Status::Pending),
+            (self.rejected, Status::Rejected),
+            (self.todo, Status::Todo),
+            (self.applied, Status::Applied),
+            (self.interviewing, Status::Interviewing),
+            (self.offer, Status::Offer),
+            (self.accepted, Status::Accepted),
+            (self.declined, Status::Declined),
+            (self.withdrawn, Status::Withdrawn),
+            (self.ghosted, Status::Ghosted),
+        ]
+        .into_iter()
+        .find_map(|(i, s)| i.map(|i| (i, s)))
+    }
+
+    /// is any of the deprecated flags given
+    fn has_legacy_flags(&self) -> bool {
+        self.status_change().is_some()
+            || self.info.is_some()
+            || self.info_change.is_some()
+            || self.stage_change.is_some()
+            || self.add.is_some()
+            || self.search.is_some()
+            || self.open
+            || self.tui
+            || self.all
+    }
+
+    /// the command to run, the deprecated flags are turned into their command with a hint
+    fn command(&mut self) -> anyhow::Result<Command> {
+        if let Some(command) = self.command.take() {
+            if self.has_legacy_flags() {
+                return Err(exit::invalid(
+                    "The deprecated flags cannot be used together with a command",
+                ));
+            }
+            return Ok(command);
+        }
+        let (command, hint) = if let Some((index, status)) = self.status_change() {
+            let status_name = status.to_string().to_lowercase();
+            (
+                Command::Status {
+                    jobs: index.map(|i| Selection::Indices(vec![i..=i])),
+                    status,
+                },
+                match index {
+                    Some(index) => format!("status {} {}", index, status_name),
+                    None => format!("status {}", status_name),
+                },
+            )
+        } else if let Some(v) = self.info_change.take() {
+            let index = parse_index(&v)?;
+            (
+                Command::Edit {
+                    jobs: Some(Selection::Indices(vec![index..=index])),
+                    name: None,
+                    subname: None,
+                    place: None,
+                    info: Some(v[1].clone()),
+                    interactive: false,
+                },
+                format!("edit {} --info <Info>", index),
+            )
+        } else if let Some(v) = self.stage_change.take() {
+            let index = parse_index(&v)?;
+            (
+                Command::Stage {
+                    command: StageCommand::Set {
+                        jobs: Some(Selection::Indices(vec![index..=index])),
+                        stage: v[1].clone(),
+                    },
+                },
+                format!("stage set {} <Stage>", index),
+            )
+        } else if self.open {
+            (Command::Open, "open".to_string())
+        } else if let Some(mut v) = self.add.take() {
+            let info = (v.len() > 2).then(|| v.remove(2));
+            (
+                Command::Add {
+                    job: NewJob {
+                        job: Some(v.remove(1)),
+                        company: Some(v.remove(0)),
+                        place: None,
+                        info,
+                        stage: None,
+                        status: Status::Todo,
+                        date: None,
+                        interactive: false,
+                    },
+                },
+                "add <Company Name> <Sub Name>".to_string(),
+            )
+        } else if let Some(name) = self.search.take() {
+            (
+                Command::Search {
+                    filters: Filters {
+                        query: vec![name],
+                        ..Default::default()
+                    },
+                    order: Order::default(),
+                    format: Format::Table,
+                    columns: Vec::new(),
+                },
+                "search <name>".to_string(),
+            )
+        } else if let Some(index) = self.info {
+            (
+                Command::Info {
+                    job: index.map(|i| Selection::Indices(vec![i..=i])),
+                    format: Format::Table,
+                },
+                match index {
+                    Some(index) => format!("info {}", index),
+                    None => "info".to_string(),
+                },
+            )
+        } else if self.tui {
+            (Command::Tui, "tui".to_string())
+        } else if self.all {
+            (
+                Command::List {
+                    all: true,
+                    filters: Filters::default(),
+                    order: Order::default(),
+                    format: Format::Table,
+                    columns: Vec::new(),
+                },
+                "list --all".to_string(),
+            )
+        } else {
+            return Ok(Command::List {
+                all: false,
+                filters: Filters::default(),
+                order: Order::default(),
+                format: Format::Table,
+                columns: Vec::new(),
+            });
+        };
+        eprintln!(
+            "{}",
+            format!("This flag is deprecated, use `job-data {}`", hint).yellow()
         );
+        Ok(command)
     }
+}
 
-    for (i, record) in rdr.0.iter().enumerate() {
-        // we want to keep the record numbers the same
-        if show_all || record.status == Status::Pending || record.status == Status::Todo {
-            record.print(i, truncate)?;
+impl Command {
+    /// does the command change any data
+    fn is_mutating(&self) -> bool {
+        match self {
+            Command::List { .. }
+            | Command::Info { .. }
+            | Command::Search { .. }
+            | Command::Report { .. }
+            | Command::Completions { .. }
+            | Command::Man { .. } => false,
+            Command::Add { .. }
+            | Command::Edit { .. }
+            | Command::Status { .. }
+            | Command::Stage { .. }
+            | Command::Tui
+            | Command::Open
+            | Command::Merge { .. } => true,
+            Command::Doctor { fix, .. } => *fix,
+            Command::Ghosted { apply, .. } => *apply,
+            Command::Offers { command } => matches!(command, OffersCommand::Set { .. }),
         }
     }
+}
+
+/// parse a date given on the command line
+fn parse_date(s: &str) -> Result<Date, String> {
+    Date::parse(s, &FORMAT)
+        .or_else(|_| Date::parse(s, format_description!("[year]-[month]-[day]")))
+        .map_err(|_| format!("{} is not a date of the form dd-mm-yyyy or yyyy-mm-dd", s))
+}
+
+/// the records we show together with their index
+fn rows<'a>(rdr: &'a Records, show_all: bool, query: &Query) -> Vec<(usize, &'a Record)> {
+    // we want to keep the record numbers the same
+    rdr.iter()
+        .enumerate()
+        .filter(|(_, r)| show_all || r.status.is_open())
+        .filter(|(_, r)| query.matches(r))
+        .collect()
+}
+
+/// print the stats of `stats` and the `columns` of the `rows`, highlighting what the `query` matches
+fn print(
+    stats: &[&Record],
+    rows: &[(usize, &Record)],
+    columns: &[Column],
+    show_time: bool,
+    query: &Query,
+) -> anyhow::Result<()> {
+    print_stats(stats)?;
+    table::print(rows, columns, show_time, query);
     Ok(())
 }
 
 /// print the stats
-fn print_stats(rdr: &Records) -> anyhow::Result<()> {
-    let vals = rdr.0.iter().fold(HashMap::new(), |mut red, elem| {
+fn print_stats(records: &[&Record]) -> anyhow::Result<()> {
+    let vals = records.iter().fold(HashMap::new(), |mut red, elem| {
         let val = red.get(&elem.status).unwrap_or(&0);
         red.insert(&elem.status, val + 1);
         red
@@ -121,12 +798,12 @@ fn print_stats(rdr: &Records) -> anyhow::Result<()> {
     println!("-------------------STATS----------------------------------------");
     for (key, val) in vals.iter() {
         let key_print = key.print();
-        let percentage: f64 = (*val as f64) / (rdr.0.len() as f64);
+        let percentage: f64 = (*val as f64) / (records.len() as f64);
         print!(
             "{}: {}/{} ({:.1}%)| ",
             key_print,
             val,
-            rdr.0.len(),
+            records.len(),
             percentage * 100_f64
         );
     }
@@ -134,128 +811,502 @@ fn print_stats(rdr: &Records) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// ask if we should change the status
-fn ask_if_change_status(rdr: &Records, index: usize, new_stage: &Status) -> bool {
-    let rec = rdr.get(index).unwrap();
-    let ans = Confirm::new(&format!(
-        "Do you want to change {} | {} from {} to {}",
-        rec.name, rec.subname, rec.status, new_stage
-    ))
-    .with_default(false)
-    .prompt();
-
-    match ans {
-        Ok(true) => true,
-        Ok(false) => false,
-        Err(_) => false,
+/// ask why the status changed, `None` if the user skips it or we cannot ask
+fn ask_transition_comment() -> Option<String> {
+    if !prompt::interactive() {
+        return None;
+    }
+    Text::new("Why did the status change (Esc to skip)")
+        .prompt_skippable()
+        .ok()
+        .flatten()
+        .filter(|c| !c.is_empty())
+}
+
+/// ask why we got rejected, `None` if the user skips it or we cannot ask
+fn ask_rejection_reason() -> Option<(Option<String>, String)> {
+    if !prompt::interactive() {
+        return None;
+    }
+    let reasons = config::get().rejection.reasons.clone();
+    let reason = if reasons.is_empty() {
+        None
+    } else {
+        Select::new("Why did we get rejected (Esc to skip)", reasons)
+            .prompt_skippable()
+            .ok()?
+    };
+    let comment = Text::new("Comment (Esc to skip)")
+        .prompt_skippable()
+        .ok()?
+        .unwrap_or_default();
+    if reason.is_none() && comment.is_empty() {
+        None
+    } else {
+        Some((reason, comment))
+    }
+}
+
+/// ask why the status changed to `status`, the result stores the answers in a record
+fn ask_why(status: Status) -> impl Fn(&mut Record) {
+    let (rejection, comment) = if status == Status::Rejected {
+        (ask_rejection_reason(), None)
+    } else {
+        (None, ask_transition_comment())
+    };
+    move |record| {
+        if let Some((reason, comment)) = rejection.clone() {
+            record.set_rejection(reason, comment);
+        } else if let Some(comment) = comment.clone() {
+            record.comment_transition(comment);
+        }
     }
 }
 
-/// ask if we should change
-fn ask_if_change(rdr: &Records, index: usize) -> bool {
-    let rec = rdr.get(index).unwrap();
-    let ans = Confirm::new(&format!(
-        "Do you want to change {} | {}",
-        rec.name, rec.subname,
-    ))
-    .with_default(false)
-    .prompt();
-
-    match ans {
-        Ok(true) => true,
-        Ok(false) => false,
-        Err(_) => false,
+/// change the status of the selected jobs, either all of them or none
+fn change_status(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    status: Status,
+    force: bool,
+) -> anyhow::Result<Outcome> {
+    let mut indices = selection::indices(jobs, rdr, |r| {
+        r.status != status && (force || workflow::allows(r.status, status))
+    })?;
+    indices.retain(|i| {
+        let record = rdr.get(*i).unwrap();
+        if record.status == status {
+            println!("{} | {} is already {}", record.name, record.subname, status);
+        }
+        record.status != status
+    });
+    for i in &indices {
+        let record = rdr.get(*i).unwrap();
+        let current = record.status;
+        if !force && !workflow::allows(current, status) {
+            let allowed = workflow::next(current)
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            return Err(exit::invalid(format!(
+                "Cannot change {} | {} from {} to {}, allowed are: {}. Use --force to change it anyway",
+                record.name, record.subname, current, status, allowed
+            )));
+        }
+    }
+    let change = match indices[..] {
+        [index] => format!(" from {} to {}", rdr.get(index).unwrap().status, status),
+        _ => format!(" to {}", status),
+    };
+    if indices.is_empty() || !selection::confirm(rdr, &indices, "change", &change)? {
+        return Ok(Outcome::Unchanged);
+    }
+    // we ask once, the answers hold for all jobs
+    let explain = ask_why(status);
+    for i in indices {
+        let record = rdr.get_mut(i).unwrap();
+        record.set_status(status);
+        explain(record);
     }
+    rdr.write()?;
+    Ok(Outcome::Done)
 }
 
-fn change_status(rdr: &mut Records, index: usize, status: Status) -> anyhow::Result<()> {
-    if ask_if_change_status(rdr, index, &status) {
-        rdr.get_mut(index).unwrap().set_status(status);
-        rdr.write()?;
+/// print everything we know about the record at `index`
+fn print_info(rdr: &Records, index: usize, show_time: bool, format: Format) -> anyhow::Result<()> {
+    let res = rdr.find(index)?;
+    if format != Format::Table {
+        return output::write(format, &[(index, res)], &[]);
+    }
+    print(
+        &[res],
+        &[(index, res)],
+        table::ALL,
+        show_time,
+        &Query::default(),
+    )?;
+    for t in &res.transitions {
+        let comment = if t.comment.is_empty() {
+            String::new()
+        } else {
+            format!(": {}", t.comment)
+        };
+        println!(
+            "Changed: {} {} -> {}{}",
+            t.date.format(show_time),
+            t.from.name(),
+            t.to.name(),
+            comment
+        );
+    }
+    for stage in &res.stages {
+        println!(
+            "Stage: {} {}: {}",
+            stage.date.format(&FORMAT)?,
+            stage,
+            stage.result
+        );
+    }
+    if let Some(rejection) = &res.rejection {
+        println!("Rejected: {}", rejection);
+    }
+    if let Some(offer) = &res.offer {
+        println!("Offer: {}", offer);
     }
     Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+/// change a single job in the wizard, starting with the given fields
+fn edit_interactive(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    fields: [(&str, Option<String>); 4],
+    force: bool,
+) -> anyhow::Result<Outcome> {
+    let Some(index) = selection::single(jobs, rdr, |_| true)? else {
+        return Ok(Outcome::Unchanged);
+    };
+    let record = rdr.get(index).unwrap();
+    let [name, subname, place, info] = fields.map(|(_, new)| new);
+    let current = Answers {
+        name: name.unwrap_or(record.name.clone()),
+        subname: subname.unwrap_or(record.subname.clone()),
+        place: place.unwrap_or(record.place.clone()),
+        info: info.unwrap_or(record.additional_info.clone()),
+        stage: record.stage.clone(),
+        status: record.status,
+        date: None,
+    };
+    let mut statuses = if force {
+        workflow::statuses()
+    } else {
+        workflow::next(record.status)
+    };
+    statuses.retain(|s| *s != record.status);
+    statuses.insert(0, record.status);
+    let Some(answers) = wizard::ask(rdr, current, &statuses)? else {
+        return Ok(Outcome::Unchanged);
+    };
+    if !wizard::confirm(&answers, "Do you want to save the changes")? {
+        return Ok(Outcome::Unchanged);
+    }
+    let explain = (answers.status != record.status).then(|| ask_why(answers.status));
+    let record = rdr.get_mut(index).unwrap();
+    record.name = answers.name;
+    record.subname = answers.subname;
+    record.place = answers.place;
+    record.additional_info = answers.info;
+    record.stage = answers.stage;
+    match explain {
+        Some(explain) => {
+            record.set_status(answers.status);
+            explain(record);
+        }
+        None => record.update_date(),
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
+
+/// change the given fields of the selected jobs
+fn edit(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    fields: [(&str, Option<String>); 4],
+) -> anyhow::Result<Outcome> {
+    let indices = selection::indices(jobs, rdr, |_| true)?;
+    if fields.iter().all(|(_, new)| new.is_none()) {
+        return Err(exit::invalid(
+            "Nothing to change, see `job-data edit --help`",
+        ));
+    }
+    for (i, (field, new)) in fields.iter().enumerate() {
+        let Some(new) = new else {
+            continue;
+        };
+        if let [index] = indices[..] {
+            let record = rdr.get(index).unwrap();
+            let current = [
+                &record.name,
+                &record.subname,
+                &record.place,
+                &record.additional_info,
+            ];
+            println!("Chainging {} from {} to {}", field, current[i], new);
+        } else {
+            println!("Chainging {} to {}", field, new);
+        }
+    }
+    if indices.is_empty() || !selection::confirm(rdr, &indices, "change", "")? {
+        return Ok(Outcome::Unchanged);
+    }
+    let [name, subname, place, info] = fields.map(|(_, new)| new);
+    for i in indices {
+        let record = rdr.get_mut(i).unwrap();
+        record.name = name.clone().unwrap_or(record.name.clone());
+        record.subname = subname.clone().unwrap_or(record.subname.clone());
+        record.place = place.clone().unwrap_or(record.place.clone());
+        record.additional_info = info.clone().unwrap_or(record.additional_info.clone());
+        record.update_date();
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
+
+fn main() -> ExitCode {
+    // the local offset is only known while we have a single thread
+    clock::init();
+    completions::complete();
+    match run() {
+        Ok(outcome) => outcome.into(),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            exit::code(&e)
+        }
+    }
+}
+
+/// run the command from the command line
+fn run() -> anyhow::Result<Outcome> {
+    let mut cli = Cli::parse();
+    config::load()?;
+    prompt::assume_yes(cli.yes);
+    let command = cli.command()?;
+    if let Some(date) = cli.as_of {
+        if command.is_mutating() {
+            return Err(exit::invalid(
+                "--as-of only shows the data, it cannot change it",
+            ));
+        }
+        clock::set(FixedClock::end_of(date))?;
+    }
+
+    if let Command::Merge {
+        ours,
+        theirs,
+        base,
+        output,
+    } = &command
+    {
+        return merge::run(
+            ours,
+            theirs,
+            base.as_deref(),
+            output.as_ref().unwrap_or(ours),
+        );
+    }
+    if let Command::Completions { shell } = command {
+        completions::write(shell)?;
+        return Ok(Outcome::Done);
+    }
+    if let Command::Man { dir } = &command {
+        completions::man(dir.as_deref())?;
+        return Ok(Outcome::Done);
+    }
+    if let Command::Open = command {
+        open::that(PATH.clone()).context("Could not open file")?;
+        return Ok(Outcome::Done);
+    }
 
     let mut rdr = Records::load()?;
+    if let Some(date) = cli.as_of {
+        rdr.as_of(date);
+    }
+    let needs_dates = !matches!(
+        command,
+        Command::Doctor { .. }
+            | Command::Report { .. }
+            | Command::Offers { .. }
+            | Command::Ghosted { .. }
+    );
+    if needs_dates && rdr.iter().any(|r| r.dates().is_empty()) {
+        anyhow::bail!("Some records have no date, please run `job-data doctor --fix`");
+    }
 
-    if let Some(i) = cli.pending {
-        change_status(&mut rdr, i, Status::Pending)?;
-    } else if let Some(i) = cli.rejected {
-        change_status(&mut rdr, i, Status::Rejected)?;
-    } else if let Some(i) = cli.todo {
-        change_status(&mut rdr, i, Status::Todo)?;
-    } else if let Some(v) = cli.info_change {
-        if let Ok(i) = v.first().unwrap().parse::<usize>() {
-            println!(
-                "Chainging from {} to {}",
-                rdr.get(i).unwrap().additional_info,
-                &v.get(1).unwrap().to_string(),
-            );
-            if ask_if_change(&rdr, i) {
-                rdr.get_mut(i).unwrap().additional_info = v.get(1).unwrap().to_string();
-                rdr.get_mut(i).unwrap().update_date();
-                rdr.write()?;
+    let outcome = match command {
+        Command::List {
+            all,
+            filters,
+            order,
+            format,
+            columns,
+        } => {
+            let query = filters.query()?;
+            let mut rows = rows(&rdr, all || query.has_status(), &query);
+            order.sort(&mut rows, &query);
+            if format != Format::Table {
+                output::write(format, &rows, &columns)?;
+                return Ok(Outcome::Done);
             }
-        } else {
-            println!("Not a valid integer");
+            print(
+                &rdr.iter().collect::<Vec<_>>(),
+                &rows,
+                if columns.is_empty() {
+                    table::LIST
+                } else {
+                    &columns
+                },
+                cli.times,
+                &query,
+            )?;
+            let ghosted = staleness::ghosted(&rdr).len();
+            if ghosted > 0 {
+                println!(
+                    "{} pending applications look ghosted, see `job-data ghosted`",
+                    ghosted
+                );
+            }
+            Outcome::Done
         }
-    } else if let Some(v) = cli.stage_change {
-        if let Ok(i) = v.first().unwrap().parse::<usize>() {
+        Command::Add { mut job } => {
+            if job.interactive {
+                match job.ask(&rdr)? {
+                    Some(answered) => job = answered,
+                    None => return Ok(Outcome::Unchanged),
+                }
+            }
+            let index = rdr.add(job.record()?);
+            rdr.write()?;
+            let record = rdr.get(index).unwrap();
+            print(
+                &rdr.iter().collect::<Vec<_>>(),
+                &[(index, record)],
+                table::LIST,
+                cli.times,
+                &Query::default(),
+            )?;
             println!(
-                "Chainging from {} to {}",
-                rdr.get(i).unwrap().stage,
-                &v.get(1).unwrap(),
+                "Added {} | {} with the index {}",
+                record.name.bold(),
+                record.subname.bold(),
+                index
             );
-            if ask_if_change(&rdr, i) {
-                rdr.get_mut(i)
-                    .unwrap()
-                    .set_stage(v.get(1).unwrap().to_string());
-                rdr.write()?;
+            Outcome::Done
+        }
+        Command::Edit {
+            jobs,
+            name,
+            subname,
+            place,
+            info,
+            interactive,
+        } => {
+            let fields = [
+                ("name", name),
+                ("subname", subname),
+                ("place", place),
+                ("info", info),
+            ];
+            if interactive {
+                edit_interactive(&mut rdr, jobs.as_ref(), fields, cli.force)?
+            } else {
+                edit(&mut rdr, jobs.as_ref(), fields)?
             }
-        } else {
-            println!("Not a valid integer");
         }
-    } else if cli.open {
-        open::that(PATH.clone()).context("Could not open file")?;
-    } else if let Some(v) = cli.add {
-        let r = Record::new(v.first().unwrap().to_string(), v.get(1).unwrap().to_string(), String::new());
-        rdr.0.push(r);
-        rdr.write()?;
-        print(&rdr, true, true)?;
-        return Ok(());
-    } else if let Some(c) = cli.search {
-        let res = rdr
-            .0
-            .into_iter()
-            .filter(|r| r.name.contains(&c))
-            .collect::<Vec<Record>>();
-        print(&Records(res), false, true)?;
-        return Ok(());
-    } else if let Some(c) = cli.info {
-        if let Some(res) = rdr.get(c) {
-            let r = vec![res.clone()];
-            print(&Records(r), false, true)?;
-        } else {
-            println!("Could not find record");
+        Command::Status { jobs, status } => {
+            change_status(&mut rdr, jobs.as_ref(), status, cli.force)?
         }
-        return Ok(());
-    } else if cli.tui {
-        match gui::run(&mut rdr)? {
+        Command::Stage { command } => match command {
+            StageCommand::Add { jobs, name, label } => {
+                stages::add(&mut rdr, jobs.as_ref(), &name, label)?
+            }
+            StageCommand::Result { jobs, result } => {
+                stages::set_result(&mut rdr, jobs.as_ref(), result)?
+            }
+            StageCommand::Set { jobs, stage } => stages::set(&mut rdr, jobs.as_ref(), stage)?,
+        },
+        Command::Info { job, format } => {
+            let Some(index) = selection::single(job.as_ref(), &rdr, |_| true)? else {
+                return Ok(Outcome::Unchanged);
+            };
+            print_info(&rdr, index, cli.times, format)?;
+            Outcome::Done
+        }
+        Command::Search {
+            filters,
+            order,
+            format,
+            columns,
+        } => {
+            let query = filters.query()?;
+            let mut res = rows(&rdr, true, &query);
+            order.sort(&mut res, &query);
+            if format != Format::Table {
+                output::write(format, &res, &columns)?;
+                return Ok(Outcome::Done);
+            }
+            print(
+                &res.iter().map(|(_, r)| *r).collect::<Vec<_>>(),
+                &res,
+                if columns.is_empty() {
+                    table::ALL
+                } else {
+                    &columns
+                },
+                cli.times,
+                &query,
+            )?;
+            Outcome::Done
+        }
+        Command::Tui => match gui::run(&mut rdr, cli.times)? {
             Save::Save => {
                 println!("Writing");
                 rdr.write()?;
+                Outcome::Done
             }
             Save::DoNotSave => {
                 println!("We did not save");
+                Outcome::Unchanged
             }
+        },
+        Command::Doctor { fix, format } => doctor::run(&mut rdr, fix, format)?,
+        Command::Report { report } => {
+            match report {
+                Report::Rejections { format } => report::rejections(&rdr, format)?,
+                Report::Funnel { format } => report::funnel(&rdr, format)?,
+            }
+            Outcome::Done
         }
-        return Ok(());
-    }
-
-    print(&rdr, true, cli.all)?;
+        Command::Offers { command } => match command {
+            OffersCommand::Compare { score, format } => {
+                offers::compare(&rdr, score, format)?;
+                Outcome::Done
+            }
+            OffersCommand::Set {
+                job,
+                base,
+                bonus,
+                equity,
+                benefits,
+                start,
+                deadline,
+            } => {
+                let Some(index) =
+                    selection::single(job.as_ref(), &rdr, |r| r.status == Status::Offer)?
+                else {
+                    return Ok(Outcome::Unchanged);
+                };
+                offers::set(
+                    &mut rdr,
+                    index,
+                    offers::OfferChange {
+                        base_salary: base,
+                        bonus,
+                        equity,
+                        benefits,
+                        start_date: start,
+                        deadline,
+                    },
+                )?
+            }
+        },
+        Command::Ghosted { apply, format } => staleness::run_ghosted(&mut rdr, apply, format)?,
+        Command::Merge { .. }
+        | Command::Open
+        | Command::Completions { .. }
+        | Command::Man { .. } => unreachable!(),
+    };
 
-    Ok(())
+    Ok(outcome)
 }
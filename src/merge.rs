@@ -0,0 +1,489 @@
+use std::{collections::HashSet, path::Path};
+
+use anyhow::Context;
+use inquire::Select;
+use serde_json::{Map, Value};
+use crate::{
+    exit::{self, Outcome},
+    prompt,
+    records::{Record, Records, Timestamp},
+};
+
+/// The field holding the dates, we always merge it by taking the union
+const DATE_FIELD: &str = "LastActionDate";
+
+/// The fields holding the history of a job, we merge them like the dates
+const HISTORY_FIELDS: [&str; 2] = ["Transitions", "Stages"];
+
+/// Which side of a merge we take
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Side {
+    /// the local records
+    Ours,
+    /// the records from the other file
+    Theirs,
+}
+
+/// A single field both sides changed differently
+#[derive(Clone, Debug)]
+pub(crate) struct FieldConflict {
+    /// the name of the field as stored in the file
+    pub(crate) field: String,
+    /// our value, `None` if we do not have the field
+    pub(crate) ours: Option<Value>,
+    /// their value, `None` if they do not have the field
+    pub(crate) theirs: Option<Value>,
+}
+
+/// All conflicting fields of one merged record
+#[derive(Clone, Debug)]
+pub(crate) struct Conflict {
+    /// the index of the record in the merge
+    pub(crate) index: usize,
+    /// the conflicting fields
+    pub(crate) fields: Vec<FieldConflict>,
+}
+
+/// The result of merging two lists of records
+pub(crate) struct Merge {
+    /// the merged records as json objects together with the union of their dates
+    records: Vec<(Map<String, Value>, Vec<Timestamp>)>,
+    /// the conflicts we could not resolve, the records hold our value until they are resolved
+    pub(crate) conflicts: Vec<Conflict>,
+    /// how many records only they had
+    pub(crate) added: usize,
+    /// how many records were removed because one side deleted them
+    pub(crate) deleted: usize,
+    /// how many records differed between both sides
+    pub(crate) merged: usize,
+}
+
+/// the key we use to find the same job in different files
+fn key(r: &Record) -> (String, String) {
+    (
+        r.name.trim().to_lowercase(),
+        r.subname.trim().to_lowercase(),
+    )
+}
+
+/// the record as a json object
+fn to_map(r: &Record) -> Map<String, Value> {
+    match serde_json::to_value(r).expect("Record is always serializable") {
+        Value::Object(m) => m,
+        _ => unreachable!(),
+    }
+}
+
+/// find the first record with the same key that is not used yet
+fn find(records: &[Record], used: &mut HashSet<usize>, r: &Record) -> Option<usize> {
+    let k = key(r);
+    let i = records
+        .iter()
+        .enumerate()
+        .position(|(i, other)| !used.contains(&i) && key(other) == k)?;
+    used.insert(i);
+    Some(i)
+}
+
+/// the sorted union of the dates of both records
+fn union_dates(ours: &Record, theirs: &Record) -> Vec<Timestamp> {
+    let mut dates = ours
+        .dates()
+        .iter()
+        .chain(theirs.dates())
+        .copied()
+        .collect::<Vec<Timestamp>>();
+    dates.sort_unstable();
+    dates.dedup();
+    dates
+}
+
+/// the union of the entries of both sides, an entry with the same `key` on both sides is kept once.
+/// If it differs we take the changed one, compared to `base`, or the `newer` side without a base
+fn union<T: Clone + PartialEq, K: PartialEq>(
+    base: Option<&[T]>,
+    ours: &[T],
+    theirs: &[T],
+    newer: Option<Side>,
+    key: impl Fn(&T) -> K,
+) -> Vec<T> {
+    let in_base = |entry: &T| base.is_some_and(|b| b.contains(entry));
+    let mut entries = ours.to_vec();
+    for t in theirs {
+        match entries.iter().position(|o| key(o) == key(t)) {
+            None => entries.push(t.clone()),
+            Some(i) if entries[i] == *t => {}
+            Some(i) => {
+                let take_theirs = if in_base(&entries[i]) {
+                    true
+                } else if in_base(t) {
+                    false
+                } else {
+                    newer == Some(Side::Theirs)
+                };
+                if take_theirs {
+                    entries[i] = t.clone();
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// a history as a json value, `None` if it is empty like in the file
+fn history<T: serde::Serialize>(entries: Vec<T>) -> Option<Value> {
+    (!entries.is_empty()).then(|| serde_json::to_value(entries).expect("always serializable"))
+}
+
+/// merge one record, returning the merged object and the fields we could not resolve
+fn merge_record(
+    base_record: Option<&Record>,
+    ours: &Record,
+    theirs: &Record,
+) -> (Map<String, Value>, Vec<FieldConflict>) {
+    let base = base_record.map(to_map);
+    let ours_map = to_map(ours);
+    let theirs_map = to_map(theirs);
+    // without a base we trust the side that saw the last action
+    let newer = match ours.dates().last().cmp(&theirs.dates().last()) {
+        std::cmp::Ordering::Less => Some(Side::Theirs),
+        std::cmp::Ordering::Greater => Some(Side::Ours),
+        std::cmp::Ordering::Equal => None,
+    };
+
+    let mut result = ours_map.clone();
+    let mut conflicts = Vec::new();
+    let fields = ours_map
+        .keys()
+        .chain(theirs_map.keys().filter(|k| !ours_map.contains_key(*k)))
+        .filter(|k| *k != DATE_FIELD && !HISTORY_FIELDS.contains(&k.as_str()))
+        .cloned()
+        .collect::<Vec<String>>();
+    for field in fields {
+        let o = ours_map.get(&field);
+        let t = theirs_map.get(&field);
+        if o == t {
+            continue;
+        }
+        let side = match &base {
+            Some(b) if b.get(&field) == o => Some(Side::Theirs),
+            Some(b) if b.get(&field) == t => Some(Side::Ours),
+            Some(_) => None,
+            None => newer,
+        };
+        match side {
+            Some(Side::Ours) => {}
+            Some(Side::Theirs) => set(&mut result, &field, t.cloned()),
+            None => conflicts.push(FieldConflict {
+                field,
+                ours: o.cloned(),
+                theirs: t.cloned(),
+            }),
+        }
+    }
+
+    let mut transitions = union(
+        base_record.map(|b| b.transitions.as_slice()),
+        &ours.transitions,
+        &theirs.transitions,
+        newer,
+        |t| (t.date, t.from, t.to),
+    );
+    transitions.sort_by_key(|t| t.date);
+    set(&mut result, "Transitions", history(transitions));
+    let mut stages = union(
+        base_record.map(|b| b.stages.as_slice()),
+        &ours.stages,
+        &theirs.stages,
+        newer,
+        |s| (s.name.clone(), s.label.clone(), s.date),
+    );
+    stages.sort_by_key(|s| s.date);
+    set(&mut result, "Stages", history(stages));
+    (result, conflicts)
+}
+
+/// set or remove a field
+fn set(map: &mut Map<String, Value>, field: &str, value: Option<Value>) {
+    if let Some(v) = value {
+        map.insert(field.to_string(), v);
+    } else {
+        map.remove(field);
+    }
+}
+
+impl Merge {
+    /// merge `theirs` into `ours`, `base` is the common ancestor if we know it
+    pub(crate) fn new(base: Option<&[Record]>, ours: &[Record], theirs: &[Record]) -> Self {
+        let mut merge = Merge {
+            records: Vec::new(),
+            conflicts: Vec::new(),
+            added: 0,
+            deleted: 0,
+            merged: 0,
+        };
+        let mut used_theirs = HashSet::new();
+        let mut used_base = HashSet::new();
+        for o in ours {
+            let b = base.and_then(|b| find(b, &mut used_base, o).map(|i| &b[i]));
+            match find(theirs, &mut used_theirs, o) {
+                Some(i) => {
+                    let t = &theirs[i];
+                    let (map, fields) = merge_record(b, o, t);
+                    if o != t {
+                        merge.merged += 1;
+                    }
+                    if !fields.is_empty() {
+                        merge.conflicts.push(Conflict {
+                            index: merge.records.len(),
+                            fields,
+                        });
+                    }
+                    merge.records.push((map, union_dates(o, t)));
+                }
+                // they deleted it and we did not change it
+                None if b == Some(o) => merge.deleted += 1,
+                None => merge.records.push((to_map(o), o.dates().to_vec())),
+            }
+        }
+        for (i, t) in theirs.iter().enumerate() {
+            if used_theirs.contains(&i) {
+                continue;
+            }
+            let b = base.and_then(|b| find(b, &mut used_base, t).map(|i| &b[i]));
+            if b == Some(t) {
+                // we deleted it and they did not change it
+                merge.deleted += 1;
+            } else {
+                merge.added += 1;
+                merge.records.push((to_map(t), t.dates().to_vec()));
+            }
+        }
+        merge
+    }
+
+    /// take the value of `side` for the conflicting field
+    pub(crate) fn resolve(&mut self, conflict: &Conflict, field: &FieldConflict, side: Side) {
+        let value = match side {
+            Side::Ours => field.ours.clone(),
+            Side::Theirs => field.theirs.clone(),
+        };
+        set(&mut self.records[conflict.index].0, &field.field, value);
+    }
+
+    /// the merged record a conflict belongs to
+    pub(crate) fn record(&self, conflict: &Conflict) -> &Map<String, Value> {
+        &self.records[conflict.index].0
+    }
+
+    /// finish the merge, conflicts that were not resolved keep our value
+    pub(crate) fn finish(self) -> anyhow::Result<Vec<Record>> {
+        self.records
+            .into_iter()
+            .map(|(map, dates)| {
+                let mut r: Record = serde_json::from_value(Value::Object(map))?;
+                *r.dates_mut() = dates;
+                Ok(r)
+            })
+            .collect()
+    }
+}
+
+/// show a json value to the user
+fn show(value: Option<&Value>) -> String {
+    match value {
+        None => "<missing>".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+    }
+}
+
+/// merge the files `ours` and `theirs` into `output`, asking the user how to resolve conflicts
+pub(crate) fn run(
+    ours: &Path,
+    theirs: &Path,
+    base: Option<&Path>,
+    output: &Path,
+) -> anyhow::Result<Outcome> {
+    let mut rdr = Records::load_from(ours).context("Could not load our file")?;
+    let theirs = Records::load_from(theirs).context("Could not load their file")?;
+    let base = base
+        .map(Records::load_from)
+        .transpose()
+        .context("Could not load the base file")?;
+    let mut merge = Merge::new(base.as_ref().map(|b| b.0.as_slice()), &rdr.0, &theirs.0);
+    println!(
+        "{} new records, {} deleted, {} merged, {} with conflicts",
+        merge.added,
+        merge.deleted,
+        merge.merged,
+        merge.conflicts.len()
+    );
+
+    if !merge.conflicts.is_empty() && !prompt::interactive() {
+        return Err(exit::invalid(
+            "Conflicts can only be resolved in a terminal, nothing was written",
+        ));
+    }
+    for conflict in merge.conflicts.clone() {
+        let record = merge.record(&conflict);
+        let title = format!(
+            "{} | {}",
+            show(record.get("Name")),
+            show(record.get("Subname"))
+        );
+        for field in &conflict.fields {
+            let options = vec![
+                format!("ours: {}", show(field.ours.as_ref())),
+                format!("theirs: {}", show(field.theirs.as_ref())),
+            ];
+            let ans = Select::new(&format!("{}: {} differs", title, field.field), options)
+                .raw_prompt()
+                .context("Merge aborted, nothing was written")?;
+            let side = if ans.index == 0 {
+                Side::Ours
+            } else {
+                Side::Theirs
+            };
+            merge.resolve(&conflict, field, side);
+        }
+    }
+
+    rdr.0 = merge.finish()?;
+    if !prompt::confirm(&format!(
+        "Write {} records to {}",
+        rdr.len(),
+        output.display()
+    ))? {
+        println!("We did not save");
+        return Ok(Outcome::Unchanged);
+    }
+    rdr.write_to(output)?;
+    println!("Wrote {}", output.display());
+    Ok(Outcome::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Status;
+
+    #[test]
+    fn without_base_the_newer_side_wins() {
+        let ours = Record::test("Acme", &["01-09-2026"]).with_place("Berlin");
+        let theirs = Record::test("Acme", &["01-09-2026", "05-09-2026"]).with_place("Hamburg");
+        let merge = Merge::new(None, &[ours], &[theirs]);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.merged, 1);
+        let records = merge.finish().unwrap();
+        assert_eq!(records[0].place, "Hamburg");
+    }
+
+    #[test]
+    fn without_base_equal_dates_conflict() {
+        let ours = Record::test("Acme", &["01-09-2026"]).with_place("Berlin");
+        let theirs = Record::test("Acme", &["01-09-2026"]).with_place("Hamburg");
+        let merge = Merge::new(None, &[ours], &[theirs]);
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.conflicts[0].fields[0].field, "Place");
+    }
+
+    #[test]
+    fn with_base_both_changes_are_kept() {
+        let base = Record::test("Acme", &["01-09-2026"]).with_place("Berlin");
+        let mut ours = base.clone();
+        ours.additional_info = "via referral".to_string();
+        let mut theirs = base.clone();
+        theirs.place = "Hamburg".to_string();
+        let merge = Merge::new(Some(&[base]), &[ours], &[theirs]);
+        assert!(merge.conflicts.is_empty());
+        let records = merge.finish().unwrap();
+        assert_eq!(records[0].additional_info, "via referral");
+        assert_eq!(records[0].place, "Hamburg");
+    }
+
+    #[test]
+    fn with_base_different_changes_conflict_and_keep_ours() {
+        let base = [Record::test("Acme", &["01-09-2026"]).with_place("Berlin")];
+        let ours = [Record::test("Acme", &["01-09-2026"]).with_place("Munich")];
+        let theirs = [Record::test("Acme", &["01-09-2026", "05-09-2026"]).with_place("Hamburg")];
+        let merge = Merge::new(Some(&base), &ours, &theirs);
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.finish().unwrap()[0].place, "Munich");
+
+        let mut merge = Merge::new(Some(&base), &ours, &theirs);
+        let conflict = merge.conflicts[0].clone();
+        merge.resolve(&conflict, &conflict.fields[0], Side::Theirs);
+        assert_eq!(merge.finish().unwrap()[0].place, "Hamburg");
+    }
+
+    #[test]
+    fn deleted_records_stay_deleted_if_unchanged() {
+        let base = [
+            Record::test("Acme", &["01-09-2026"]).with_place("Berlin"),
+            Record::test("Bar", &["01-09-2026"]).with_place("Hamburg"),
+        ];
+        // they deleted Acme, we deleted Bar
+        let ours = [base[0].clone()];
+        let theirs = [base[1].clone()];
+        let merge = Merge::new(Some(&base), &ours, &theirs);
+        assert_eq!(merge.deleted, 2);
+        assert!(merge.finish().unwrap().is_empty());
+    }
+
+    #[test]
+    fn deleted_records_are_kept_if_changed() {
+        let base = [Record::test("Acme", &["01-09-2026"]).with_place("Berlin")];
+        let ours = [Record::test("Acme", &["01-09-2026", "05-09-2026"]).with_place("Munich")];
+        let merge = Merge::new(Some(&base), &ours, &[]);
+        assert_eq!(merge.deleted, 0);
+        assert_eq!(merge.finish().unwrap()[0].place, "Munich");
+    }
+
+    #[test]
+    fn without_base_nothing_is_deleted() {
+        let ours = [Record::test("Acme", &["01-09-2026"]).with_place("Berlin")];
+        let theirs = [Record::test("Bar", &["01-09-2026"]).with_place("Hamburg")];
+        let merge = Merge::new(None, &ours, &theirs);
+        assert_eq!((merge.added, merge.deleted), (1, 0));
+        let names = merge
+            .finish()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Acme", "Bar"]);
+    }
+
+    #[test]
+    fn dates_are_the_sorted_union() {
+        let ours = Record::test("Acme", &["01-09-2026", "10-09-2026"]).with_place("Berlin");
+        let theirs = Record::test("Acme", &["01-09-2026", "05-09-2026"]).with_place("Berlin");
+        let records = Merge::new(None, &[ours], &[theirs]).finish().unwrap();
+        let dates = records[0]
+            .dates()
+            .iter()
+            .map(|d| d.format(false))
+            .collect::<Vec<_>>();
+        assert_eq!(dates, ["01-09-2026", "05-09-2026", "10-09-2026"]);
+    }
+
+    #[test]
+    fn transitions_are_the_sorted_union() {
+        let base = Record::test("Acme", &["01-09-2026"]).with_place("Berlin");
+        let (mut ours, mut theirs) = (base.clone(), base.clone());
+        ours.set_status(Status::Interviewing);
+        ours.transitions[0].date = Timestamp::Date(time::macros::date!(2026 - 09 - 10));
+        theirs.set_status(Status::Rejected);
+        theirs.transitions[0].date = Timestamp::Date(time::macros::date!(2026 - 09 - 05));
+        let records = Merge::new(Some(&[base]), &[ours], &[theirs])
+            .finish()
+            .unwrap();
+        let to = records[0]
+            .transitions
+            .iter()
+            .map(|t| t.to)
+            .collect::<Vec<_>>();
+        assert_eq!(to, [Status::Rejected, Status::Interviewing]);
+    }
+}
@@ -0,0 +1,272 @@
+use serde_json::Value;
+use time::Date;
+use yansi::Paint;
+
+use crate::{
+    clock, config,
+    exit::Outcome,
+    output::{self, Format},
+    prompt,
+    records::{Offer, Record, Records},
+    types::{Status, FORMAT},
+};
+
+/// The changes to an offer given on the command line, everything not given stays as it is
+#[derive(Debug, Default)]
+pub(crate) struct OfferChange {
+    pub(crate) base_salary: Option<u64>,
+    pub(crate) bonus: Option<u64>,
+    pub(crate) equity: Option<u64>,
+    pub(crate) benefits: Option<String>,
+    pub(crate) start_date: Option<Date>,
+    pub(crate) deadline: Option<Date>,
+}
+
+/// all records with an open offer together with their index, records without details get empty ones
+pub(crate) fn open_offers(rdr: &Records) -> Vec<(usize, &Record, Offer)> {
+    rdr.iter()
+        .enumerate()
+        .filter(|(_, r)| r.status == Status::Offer)
+        .map(|(i, r)| (i, r, r.offer.clone().unwrap_or_default()))
+        .collect()
+}
+
+/// is the deadline of the offer near or already passed
+pub(crate) fn deadline_is_near(offer: &Offer, today: Date) -> bool {
+    offer
+        .deadline
+        .is_some_and(|d| (d - today).whole_days() <= config::get().offers.deadline_days)
+}
+
+/// reads one amount of an offer
+type Amount = fn(&Offer) -> Option<u64>;
+
+/// score the offers relative to each other from 0 to 100 with the weights from the config
+pub(crate) fn scores(offers: &[&Offer]) -> Vec<f64> {
+    let weights = &config::get().offers.weights;
+    let parts: [(Amount, f64); 3] = [
+        (|o| o.base_salary, weights.base_salary),
+        (|o| o.bonus, weights.bonus),
+        (|o| o.equity, weights.equity),
+    ];
+    let total_weight = parts.iter().map(|(_, w)| w).sum::<f64>();
+    offers
+        .iter()
+        .map(|o| {
+            if total_weight <= 0.0 {
+                return 0.0;
+            }
+            let score = parts
+                .iter()
+                .map(|(get, weight)| {
+                    // the best offer gets the full weight, the others their share of it
+                    let max = offers.iter().filter_map(|o| get(o)).max().unwrap_or(0);
+                    if max == 0 {
+                        0.0
+                    } else {
+                        get(o).unwrap_or(0) as f64 / max as f64 * weight
+                    }
+                })
+                .sum::<f64>();
+            score / total_weight * 100.0
+        })
+        .collect()
+}
+
+/// show an amount
+pub(crate) fn amount(value: Option<u64>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// show a date
+pub(crate) fn day(value: Option<Date>) -> String {
+    value
+        .map(|d| d.format(&FORMAT).unwrap())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl std::fmt::Display for Offer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "base {}, bonus {}, equity {}, total {}, start {}, deadline {}",
+            amount(self.base_salary),
+            amount(self.bonus),
+            amount(self.equity),
+            self.total(),
+            day(self.start_date),
+            day(self.deadline),
+        )?;
+        if !self.benefits.is_empty() {
+            write!(f, ", {}", self.benefits)?;
+        }
+        Ok(())
+    }
+}
+
+/// print one line of the comparison
+fn print_row(label: &str, cells: impl IntoIterator<Item = String>) {
+    print!("{:>10}", label.bold());
+    for c in cells {
+        let c = c.chars().take(20).collect::<String>();
+        print!(" | {:^20}", c);
+    }
+    println!();
+}
+
+/// write the open offers with their total and the score if `score` is set
+fn write(
+    offers: &[(usize, &Record, Offer)],
+    scored: &[f64],
+    score: bool,
+    format: Format,
+) -> anyhow::Result<()> {
+    let mut fields = output::FIELDS.to_vec();
+    fields.push("Total");
+    if score {
+        fields.push("Score");
+    }
+    let maps = offers
+        .iter()
+        .zip(scored)
+        .map(|((i, r, o), s)| {
+            let mut map = output::to_map(*i, r);
+            // the details we do not know yet are empty like in the table
+            map.insert("Offer".to_string(), serde_json::to_value(o)?);
+            map.insert("Total".to_string(), Value::from(o.total()));
+            if score {
+                map.insert("Score".to_string(), Value::from(*s));
+            }
+            Ok(map)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    output::write_maps(format, &fields, &maps)
+}
+
+/// print all open offers side by side in `format`, best score first if `score` is set
+pub(crate) fn compare(rdr: &Records, score: bool, format: Format) -> anyhow::Result<()> {
+    let mut offers = open_offers(rdr);
+    let mut scored = scores(&offers.iter().map(|(_, _, o)| o).collect::<Vec<_>>());
+    if score {
+        let mut both = offers.into_iter().zip(scored).collect::<Vec<_>>();
+        both.sort_by(|a, b| b.1.total_cmp(&a.1));
+        (offers, scored) = both.into_iter().unzip();
+    }
+    if format != Format::Table {
+        return write(&offers, &scored, score, format);
+    }
+    if offers.is_empty() {
+        println!("No open offers, move a job to offer with `job-data status <index> offer`");
+        return Ok(());
+    }
+    let today = clock::today();
+
+    print_row("#", offers.iter().map(|(i, _, _)| i.to_string()));
+    print_row("Name", offers.iter().map(|(_, r, _)| r.name.clone()));
+    print_row("Subname", offers.iter().map(|(_, r, _)| r.subname.clone()));
+    print_row("Base", offers.iter().map(|(_, _, o)| amount(o.base_salary)));
+    print_row("Bonus", offers.iter().map(|(_, _, o)| amount(o.bonus)));
+    print_row("Equity", offers.iter().map(|(_, _, o)| amount(o.equity)));
+    print_row(
+        "Total",
+        offers.iter().map(|(_, _, o)| o.total().to_string()),
+    );
+    print_row(
+        "Benefits",
+        offers.iter().map(|(_, _, o)| o.benefits.clone()),
+    );
+    print_row("Start", offers.iter().map(|(_, _, o)| day(o.start_date)));
+    print!("{:>10}", "Deadline".bold());
+    for (_, _, o) in &offers {
+        let d = format!("{:^20}", day(o.deadline));
+        if deadline_is_near(o, today) {
+            print!(" | {}", d.red().bold());
+        } else {
+            print!(" | {}", d);
+        }
+    }
+    println!();
+    if score {
+        print_row("Score", scored.iter().map(|s| format!("{:.1}", s)));
+    }
+    Ok(())
+}
+
+/// change the offer of the record at `index`
+pub(crate) fn set(rdr: &mut Records, index: usize, change: OfferChange) -> anyhow::Result<Outcome> {
+    let record = rdr.find_mut(index)?;
+    let mut offer = record.offer.clone().unwrap_or_default();
+    offer.base_salary = change.base_salary.or(offer.base_salary);
+    offer.bonus = change.bonus.or(offer.bonus);
+    offer.equity = change.equity.or(offer.equity);
+    offer.benefits = change.benefits.unwrap_or(offer.benefits);
+    offer.start_date = change.start_date.or(offer.start_date);
+    offer.deadline = change.deadline.or(offer.deadline);
+
+    if !prompt::confirm(&format!(
+        "Do you want to set the offer of {} | {} to {}",
+        record.name, record.subname, offer
+    ))? {
+        return Ok(Outcome::Unchanged);
+    }
+    let is_offer = record.status == Status::Offer;
+    record.offer = Some(offer);
+    record.update_date();
+    rdr.write()?;
+    if !is_offer {
+        println!(
+            "The job is not at the offer status, move it with `job-data status {} offer`",
+            index
+        );
+    }
+    Ok(Outcome::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// an offer with the amounts
+    fn offer(base_salary: Option<u64>, bonus: Option<u64>, equity: Option<u64>) -> Offer {
+        Offer {
+            base_salary,
+            bonus,
+            equity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn total_adds_the_known_amounts() {
+        assert_eq!(offer(None, None, None).total(), 0);
+        assert_eq!(offer(Some(60000), None, Some(5000)).total(), 65000);
+        assert_eq!(
+            offer(Some(u64::MAX), Some(1), Some(u64::MAX)).total(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn the_best_offer_scores_100() {
+        let best = offer(Some(80000), Some(10000), Some(10000));
+        let worse = offer(Some(40000), None, Some(5000));
+        let scored = scores(&[&best, &worse]);
+        assert_eq!(scored[0], 100.0);
+        assert!(scored[1] > 0.0 && scored[1] < 100.0);
+        // nothing to compare
+        assert_eq!(scores(&[&offer(None, None, None)]), [0.0]);
+    }
+
+    #[test]
+    fn deadlines_are_near_within_the_configured_days() {
+        let today = clock::today();
+        let mut o = offer(None, None, None);
+        assert!(!deadline_is_near(&o, today));
+        o.deadline = Some(today);
+        assert!(deadline_is_near(&o, today));
+        o.deadline = Some(today + time::Duration::days(365));
+        assert!(!deadline_is_near(&o, today));
+    }
+}
@@ -0,0 +1,82 @@
+use ratatui::{
+    crossterm::event::{self, KeyCode},
+    layout::{Constraint, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Clear, Row, Table},
+    Frame,
+};
+
+use crate::{
+    clock,
+    offers::{self, amount, day},
+    types::{center, GuiState, WindowFocus},
+};
+
+/// draw the open offers, best score first, near deadlines in red
+pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
+    let area = center(
+        frame.area(),
+        Constraint::Percentage(80),
+        Constraint::Percentage(50),
+    );
+    let today = clock::today();
+    let offers = offers::open_offers(state.rdr);
+    let scores = offers::scores(&offers.iter().map(|(_, _, o)| o).collect::<Vec<_>>());
+    let mut both = offers.into_iter().zip(scores).collect::<Vec<_>>();
+    both.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let rows = both.into_iter().map(|((_, r, o), score)| {
+        let style = if offers::deadline_is_near(&o, today) {
+            Style::new().fg(Color::Red).bold()
+        } else {
+            Style::new()
+        };
+        Row::new(vec![
+            r.name.clone(),
+            r.subname.clone(),
+            amount(o.base_salary),
+            amount(o.bonus),
+            amount(o.equity),
+            o.total().to_string(),
+            format!("{:.1}", score),
+            day(o.start_date),
+            day(o.deadline),
+            o.benefits.clone(),
+        ])
+        .style(style)
+    });
+    let widths = [
+        Constraint::Length(20),
+        Constraint::Length(20),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(10),
+    ];
+    let table = Table::new(rows, widths)
+        .column_spacing(1)
+        .header(
+            Row::new(vec![
+                "Name", "Subname", "Base", "Bonus", "Equity", "Total", "Score", "Start",
+                "Deadline", "Benefits",
+            ])
+            .style(Style::new().bold()),
+        )
+        .block(Block::bordered().title("Offers"));
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+/// handle inputs for the offers
+pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('o') => {
+            state.focus = WindowFocus::Table;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,149 @@
+use std::io::Write;
+
+use serde_json::{Map, Value};
+
+use crate::{records::Record, table::Column};
+
+/// How we print records
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Format {
+    /// the colored table for humans
+    #[default]
+    Table,
+    /// a json array of all records
+    Json,
+    /// one json object per line
+    Jsonl,
+    /// comma separated values with a header
+    Csv,
+    /// tab separated values with a header
+    Tsv,
+}
+
+/// The columns of csv and tsv in the order of the data file, fields we do not know are left out
+pub(crate) const FIELDS: [&str; 12] = [
+    "Index",
+    "LastActionDate",
+    "Name",
+    "Subname",
+    "Stage",
+    "Stages",
+    "AdditionalInfo",
+    "Status",
+    "Place",
+    "Transitions",
+    "Rejection",
+    "Offer",
+];
+
+/// the record as stored in the file with its index in front
+pub(crate) fn to_map(index: usize, r: &Record) -> Map<String, Value> {
+    let mut map = Map::new();
+    map.insert("Index".to_string(), Value::from(index));
+    match serde_json::to_value(r).expect("Record is always serializable") {
+        Value::Object(m) => map.extend(m),
+        _ => unreachable!(),
+    }
+    map
+}
+
+/// the `fields` with their `values` as a map to write, for anything that is not a record
+pub(crate) fn row<const N: usize>(fields: [&str; N], values: [Value; N]) -> Map<String, Value> {
+    fields.iter().map(|f| f.to_string()).zip(values).collect()
+}
+
+/// a json value as a single csv field
+fn to_field(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+    }
+}
+
+/// the fields of the `columns`, all of them if none are given.
+/// The index always comes along, scripts need it to change the job later
+fn fields(columns: &[Column]) -> Vec<&'static str> {
+    if columns.is_empty() {
+        return FIELDS.to_vec();
+    }
+    let mut fields = columns.iter().map(|c| c.field()).collect::<Vec<_>>();
+    if !columns.contains(&Column::Index) {
+        fields.insert(0, Column::Index.field());
+    }
+    fields
+}
+
+/// write the records in a machine readable format, `Format::Table` is printed by the caller.
+/// Only the fields of the `columns` and the index if any are given
+pub(crate) fn write(
+    format: Format,
+    rows: &[(usize, &Record)],
+    columns: &[Column],
+) -> anyhow::Result<()> {
+    let fields = fields(columns);
+    let mut maps = rows.iter().map(|(i, r)| to_map(*i, r)).collect::<Vec<_>>();
+    if !columns.is_empty() {
+        for m in &mut maps {
+            *m = fields
+                .iter()
+                .filter_map(|f| m.get(*f).map(|v| (f.to_string(), v.clone())))
+                .collect();
+        }
+    }
+    write_maps(format, &fields, &maps)
+}
+
+/// write the `maps` in a machine readable format, csv and tsv write the `fields` in this order
+pub(crate) fn write_maps(
+    format: Format,
+    fields: &[&str],
+    maps: &[Map<String, Value>],
+) -> anyhow::Result<()> {
+    let mut out = std::io::stdout().lock();
+    match format {
+        Format::Table => unreachable!("the table is printed by the caller"),
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, maps)?;
+            writeln!(out)?;
+        }
+        Format::Jsonl => {
+            for m in maps {
+                serde_json::to_writer(&mut out, m)?;
+                writeln!(out)?;
+            }
+        }
+        Format::Csv | Format::Tsv => {
+            let delimiter = if format == Format::Csv { b',' } else { b'\t' };
+            let mut wtr = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(out);
+            // the header names the fields, so scripts can find them by name
+            wtr.write_record(fields)?;
+            for m in maps {
+                wtr.write_record(fields.iter().map(|k| to_field(m.get(*k))))?;
+            }
+            wtr.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_columns_keep_the_index() {
+        assert_eq!(fields(&[]), FIELDS);
+        assert_eq!(
+            fields(&[Column::Name, Column::Date]),
+            ["Index", "Name", "LastActionDate"]
+        );
+        // the index stays where it was chosen
+        assert_eq!(
+            fields(&[Column::Status, Column::Index]),
+            ["Status", "Index"]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use std::{io::IsTerminal, sync::OnceLock};
+
+use inquire::{Confirm, InquireError};
+
+use crate::exit;
+
+/// Do we answer every question with yes, set once at startup
+static ASSUME_YES: OnceLock<bool> = OnceLock::new();
+
+/// answer every question with yes from now on
+pub(crate) fn assume_yes(yes: bool) {
+    // if it was set already somebody asked before
+    let _ = ASSUME_YES.set(yes);
+}
+
+/// do we answer every question with yes
+fn yes() -> bool {
+    *ASSUME_YES.get_or_init(|| false)
+}
+
+/// is there a terminal to ask the user
+pub(crate) fn terminal() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// can we ask the user anything, optional questions are skipped otherwise
+pub(crate) fn interactive() -> bool {
+    !yes() && terminal()
+}
+
+/// ask a yes or no question, with `--yes` we do not ask and without a terminal we fail
+pub(crate) fn confirm(question: &str) -> anyhow::Result<bool> {
+    if yes() {
+        eprintln!("{} yes", question);
+        return Ok(true);
+    }
+    if !terminal() {
+        return Err(exit::invalid(format!(
+            "{}? Cannot ask without a terminal, use --yes to confirm",
+            question
+        )));
+    }
+    match Confirm::new(question).with_default(false).prompt() {
+        Ok(ans) => Ok(ans),
+        Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
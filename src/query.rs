@@ -0,0 +1,405 @@
+use std::ops::Range;
+
+use clap::ValueEnum;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use regex::{Regex, RegexBuilder};
+use time::Date;
+
+use crate::{records::Record, types::Status};
+
+thread_local! {
+    /// the fuzzy matcher keeps caches, so we only build it once
+    static FUZZY: SkimMatcherV2 = SkimMatcherV2::default().ignore_case();
+}
+
+/// How we match the words of a search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum SearchMode {
+    /// the text appears somewhere, ignoring case
+    #[default]
+    Plain,
+    /// a regular expression, ignoring case
+    Regex,
+    /// the letters appear in this order, the closer the better
+    Fuzzy,
+}
+
+impl SearchMode {
+    /// toggle the mode
+    pub(crate) fn next(self) -> SearchMode {
+        match self {
+            SearchMode::Plain => SearchMode::Regex,
+            SearchMode::Regex => SearchMode::Fuzzy,
+            SearchMode::Fuzzy => SearchMode::Plain,
+        }
+    }
+}
+
+/// Matches a word of a search in the text fields of a job
+#[derive(Clone, Debug)]
+pub(crate) enum Matcher {
+    /// plain text is a regular expression as well
+    Regex(Regex),
+    /// the pattern for fuzzy matching
+    Fuzzy(String),
+}
+
+impl Matcher {
+    /// a matcher for `text` in the given mode
+    fn new(text: &str, mode: SearchMode) -> Result<Matcher, String> {
+        let pattern = match mode {
+            SearchMode::Plain => regex::escape(text),
+            SearchMode::Regex => text.to_string(),
+            SearchMode::Fuzzy => return Ok(Matcher::Fuzzy(text.to_string())),
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Matcher::Regex)
+            .map_err(|_| format!("{} is not a valid regex", text))
+    }
+
+    /// how well the text matches, `None` if it does not match at all
+    fn score(&self, text: &str) -> Option<i64> {
+        match self {
+            Matcher::Regex(r) => {
+                let count = r.find_iter(text).count() as i64;
+                (count > 0).then_some(count)
+            }
+            Matcher::Fuzzy(pattern) => FUZZY.with(|m| m.fuzzy_match(text, pattern)),
+        }
+    }
+
+    /// the byte ranges of the text that match
+    fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            Matcher::Regex(r) => r
+                .find_iter(text)
+                .map(|m| m.range())
+                .filter(|r| !r.is_empty())
+                .collect(),
+            Matcher::Fuzzy(pattern) => {
+                let Some((_, indices)) = FUZZY.with(|m| m.fuzzy_indices(text, pattern)) else {
+                    return Vec::new();
+                };
+                // the matcher counts chars, not bytes
+                text.char_indices()
+                    .enumerate()
+                    .filter(|(i, _)| indices.contains(i))
+                    .map(|(_, (b, c))| b..b + c.len_utf8())
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A single condition of a query
+#[derive(Clone, Debug)]
+pub(crate) enum Filter {
+    /// the status is one of these
+    Status(Vec<Status>),
+    /// the last action was on or after the date
+    Since(Date),
+    /// the last action was on or before the date
+    Until(Date),
+    /// the place contains the text, ignoring case
+    Place(String),
+    /// the stage contains the text, ignoring case
+    Stage(String),
+    /// the job is open and stale or not
+    Stale(bool),
+    /// any of the text fields matches
+    Text(Matcher),
+}
+
+/// A query, all filters have to match
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Query(pub(crate) Vec<Filter>);
+
+/// does `haystack` contain `needle`, ignoring case
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// the fields we search for text
+fn text_fields(r: &Record) -> [&str; 5] {
+    [
+        &r.name,
+        &r.subname,
+        &r.stage,
+        &r.place,
+        &r.additional_info,
+    ]
+}
+
+/// a status by its name or its configured name, ignoring case
+fn parse_status(s: &str) -> Result<Status, String> {
+    Status::from_str(s, true)
+        .ok()
+        .or_else(|| Status::ALL.into_iter().find(|st| st.name().eq_ignore_ascii_case(s)))
+        .ok_or_else(|| format!("{} is not a status", s))
+}
+
+/// The keys of the filters, any other word is text even with a colon, i.e., `10:30`
+const FILTERS: [&str; 6] = ["status", "since", "until", "place", "stage", "stale"];
+
+/// is the word a filter, i.e., `status:pending`, and not text
+fn is_filter(word: &str) -> bool {
+    word.trim_start_matches('"')
+        .split_once(':')
+        .is_some_and(|(key, _)| FILTERS.contains(&key.to_lowercase().as_str()))
+}
+
+/// does the text use a filter like `status:pending`, otherwise it is only text
+pub(crate) fn has_filter(s: &str) -> bool {
+    s.split_whitespace().any(is_filter)
+}
+
+/// split the query into words, double quotes keep spaces in a word
+fn tokenize(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in s.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("missing closing quote".to_string());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+impl Filter {
+    /// does the record match
+    pub(crate) fn matches(&self, r: &Record) -> bool {
+        match self {
+            Filter::Status(statuses) => statuses.contains(&r.status),
+            // a record without a date is broken, the doctor repairs it
+            Filter::Since(date) => r.dates().last().is_some_and(|d| d.date() >= *date),
+            Filter::Until(date) => r.dates().last().is_some_and(|d| d.date() <= *date),
+            Filter::Place(place) => contains(&r.place, place),
+            Filter::Stage(stage) => contains(&r.stage, stage),
+            Filter::Stale(stale) => (r.status.is_open() && r.is_old()) == *stale,
+            Filter::Text(m) => text_fields(r).iter().any(|f| m.score(f).is_some()),
+        }
+    }
+}
+
+impl Query {
+    /// parse a query like `status:pending place:berlin since:2026-09-01 "backend dev"`,
+    /// the words without a filter are matched according to `mode`
+    pub(crate) fn parse(s: &str, mode: SearchMode) -> Result<Query, String> {
+        let mut filters = Vec::new();
+        for token in tokenize(s)? {
+            let Some((key, value)) = token.split_once(':') else {
+                filters.push(Filter::Text(Matcher::new(&token, mode)?));
+                continue;
+            };
+            let filter = match key.to_lowercase().as_str() {
+                "status" => Filter::Status(
+                    value
+                        .split(',')
+                        .map(parse_status)
+                        .collect::<Result<_, _>>()?,
+                ),
+                "since" => Filter::Since(crate::parse_date(value)?),
+                "until" => Filter::Until(crate::parse_date(value)?),
+                "place" => Filter::Place(value.to_string()),
+                "stage" => Filter::Stage(value.to_string()),
+                "stale" => match value.to_lowercase().as_str() {
+                    "yes" | "true" => Filter::Stale(true),
+                    "no" | "false" => Filter::Stale(false),
+                    _ => return Err(format!("stale is yes or no, not {}", value)),
+                },
+                // any other word with a colon is text, i.e., `10:30`, see `FILTERS`
+                _ => Filter::Text(Matcher::new(&token, mode)?),
+            };
+            filters.push(filter);
+        }
+        Ok(Query(filters))
+    }
+
+    /// a query for `text` as one phrase, matched according to `mode`
+    pub(crate) fn text(text: &str, mode: SearchMode) -> Result<Query, String> {
+        Ok(Query(vec![Filter::Text(Matcher::new(text, mode)?)]))
+    }
+
+    /// do all filters match the record
+    pub(crate) fn matches(&self, r: &Record) -> bool {
+        self.0.iter().all(|f| f.matches(r))
+    }
+
+    /// does the query ask for certain statuses, then we show closed jobs as well
+    pub(crate) fn has_status(&self) -> bool {
+        self.0.iter().any(|f| matches!(f, Filter::Status(_)))
+    }
+
+    /// does the query search for text, then we rank the results
+    pub(crate) fn has_text(&self) -> bool {
+        self.0.iter().any(|f| matches!(f, Filter::Text(_)))
+    }
+
+    /// how well the record matches the text of the query, higher is better
+    pub(crate) fn score(&self, r: &Record) -> i64 {
+        self.0
+            .iter()
+            .filter_map(|f| match f {
+                Filter::Text(m) => text_fields(r).iter().filter_map(|t| m.score(t)).max(),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// the sorted byte ranges of `text` any word of the query matches, to highlight them
+    pub(crate) fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = self
+            .0
+            .iter()
+            .filter_map(|f| match f {
+                Filter::Text(m) => Some(m.ranges(text)),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| r.start);
+        // merge overlapping ranges so we do not highlight twice
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        merged
+    }
+
+    /// both queries have to match
+    pub(crate) fn and(mut self, other: Query) -> Query {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    /// a pending record in berlin
+    fn record() -> Record {
+        let mut r = Record::test("Acme", &["01-09-2026"])
+            .with_subname("Backend Developer")
+            .with_stage("first interview");
+        r.additional_info = "via referral".to_string();
+        r
+    }
+
+    #[test]
+    fn tokenize_keeps_quoted_spaces() {
+        assert_eq!(
+            tokenize(r#"status:pending  "backend dev" place:"new york""#).unwrap(),
+            ["status:pending", "backend dev", "place:new york"]
+        );
+        assert!(tokenize("").unwrap().is_empty());
+        assert!(tokenize(r#""backend"#).is_err());
+    }
+
+    #[test]
+    fn parse_filters_and_text() {
+        let query = Query::parse(
+            r#"status:pending,rejected since:2026-09-01 until:01-10-2026 place:berlin "backend dev""#,
+            SearchMode::Plain,
+        )
+        .unwrap();
+        assert!(
+            matches!(&query.0[0], Filter::Status(s) if *s == [Status::Pending, Status::Rejected])
+        );
+        assert!(matches!(query.0[1], Filter::Since(d) if d == date!(2026 - 09 - 01)));
+        assert!(matches!(query.0[2], Filter::Until(d) if d == date!(2026 - 10 - 01)));
+        assert!(matches!(&query.0[3], Filter::Place(p) if p == "berlin"));
+        assert!(matches!(query.0[4], Filter::Text(_)));
+        assert!(query.has_status() && query.has_text());
+    }
+
+    #[test]
+    fn parse_rejects_wrong_values() {
+        for q in ["status:nope", "since:yesterday", "stale:maybe"] {
+            assert!(Query::parse(q, SearchMode::Plain).is_err(), "{}", q);
+        }
+        assert!(Query::parse("(", SearchMode::Regex).is_err());
+    }
+
+    #[test]
+    fn words_with_a_colon_are_text_unless_they_are_a_filter() {
+        for mode in [SearchMode::Plain, SearchMode::Regex, SearchMode::Fuzzy] {
+            for q in [
+                "10:30",
+                r#""Senior: Rust""#,
+                "https://acme.com",
+                "stauts:pending",
+            ] {
+                let query = Query::parse(q, mode).unwrap();
+                assert!(matches!(query.0[..], [Filter::Text(_)]), "{}", q);
+            }
+        }
+        let mut r = record();
+        r.additional_info = "call at 10:30".to_string();
+        assert!(Query::parse("10:30", SearchMode::Plain)
+            .unwrap()
+            .matches(&r));
+        assert!(Query::parse("STATUS:pending", SearchMode::Fuzzy)
+            .unwrap()
+            .has_status());
+    }
+
+    #[test]
+    fn matches_all_filters() {
+        let r = record();
+        let matches = |q: &str| Query::parse(q, SearchMode::Plain).unwrap().matches(&r);
+        assert!(matches(""));
+        assert!(matches("status:pending place:BERLIN referral"));
+        assert!(matches(r#""backend dev""#));
+        assert!(!matches("status:rejected"));
+        assert!(!matches("place:hamburg"));
+        assert!(!matches("until:2026-08-31"));
+        assert!(matches("stage:interview since:2026-09-01"));
+    }
+
+    #[test]
+    fn modes_match_differently() {
+        let r = record();
+        let matches = |q: &str, mode| Query::parse(q, mode).unwrap().matches(&r);
+        assert!(!matches("back.*dev", SearchMode::Plain));
+        assert!(matches("back.*dev", SearchMode::Regex));
+        assert!(matches("bkdv", SearchMode::Fuzzy));
+        assert!(!matches("bkdv", SearchMode::Plain));
+    }
+
+    #[test]
+    fn ranges_are_merged_and_sorted() {
+        let query = Query::parse("end dev ckend", SearchMode::Plain).unwrap();
+        assert_eq!(query.ranges("Backend Developer"), [2..7, 8..11]);
+    }
+
+    #[test]
+    fn filters_need_a_key() {
+        assert!(has_filter("status:pending stale:yes"));
+        assert!(has_filter(r#""place:new york""#));
+        assert!(!has_filter("Acme Backend"));
+        assert!(!has_filter("10:30"));
+        assert!(!has_filter(":x"));
+        assert!(!has_filter("https://acme.com"));
+    }
+}
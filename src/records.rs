@@ -1,56 +1,260 @@
 use std::{
-    cmp::Ordering, fs::File, io::{BufReader, BufWriter}
+    cmp::Ordering, fs::File, io::{BufReader, BufWriter}, path::Path, time::SystemTime
 };
 use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, FromInto};
-use time::{Date, Duration, OffsetDateTime};
-use yansi::Paint;
+use serde_json::{Map, Value};
+use time::{format_description::well_known::Rfc3339, Date, OffsetDateTime};
 
 use crate::{
-    types::{Status, FORMAT},
+    clock, exit::NotFound, staleness,
+    types::{Status, DATE_TIME_FORMAT, FORMAT},
     PATH,
 };
 
-time::serde::format_description!(my_format, Date,"[day]-[month]-[year]");
-#[derive(Serialize, Deserialize)]
-#[serde(transparent)]
-struct MyDate(#[serde(with = "my_format")] Date);
+// dates without a time are stored like the old action dates
+time::serde::format_description!(day_format, Date, "[day]-[month]-[year]");
 
-impl From<Date> for MyDate {
-    fn from(value: Date) -> Self {
-        MyDate(value)
+/// The point in time of an action, old files only stored the date
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Timestamp {
+    /// only the date is known
+    Date(Date),
+    /// the full time with the offset it happened in
+    DateTime(OffsetDateTime),
+}
+
+impl Timestamp {
+    /// the current time
+    pub(crate) fn now() -> Self {
+        let now = clock::now();
+        // nobody needs the nanoseconds in the file
+        Timestamp::DateTime(now.replace_nanosecond(0).unwrap())
+    }
+
+    /// the date of the action in the offset it happened in
+    pub(crate) fn date(&self) -> Date {
+        match self {
+            Timestamp::Date(d) => *d,
+            Timestamp::DateTime(t) => t.date(),
+        }
+    }
+
+    /// format the timestamp, only showing the time if `show_time` is set and we know it
+    pub(crate) fn format(&self, show_time: bool) -> String {
+        match self {
+            Timestamp::DateTime(t) if show_time => t.format(&DATE_TIME_FORMAT).unwrap(),
+            _ => self.date().format(&FORMAT).unwrap(),
+        }
+    }
+}
+
+impl Ord for Timestamp {
+    /// a timestamp without time comes before all timestamps with time on the same day
+    fn cmp(&self, other: &Self) -> Ordering {
+        let time = |t: &Timestamp| match t {
+            Timestamp::Date(_) => None,
+            Timestamp::DateTime(t) => Some(*t),
+        };
+        self.date()
+            .cmp(&other.date())
+            .then_with(|| time(self).cmp(&time(other)))
     }
 }
 
-impl From<MyDate> for Date {
-    fn from(value: MyDate) -> Self {
-        value.0
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
+impl Eq for Timestamp {}
+
+impl Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = match self {
+            // we keep the old format so old versions can still read it
+            Timestamp::Date(d) => d.format(&FORMAT),
+            Timestamp::DateTime(t) => t.format(&Rfc3339),
+        }
+        .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        OffsetDateTime::parse(&s, &Rfc3339)
+            .map(Timestamp::DateTime)
+            .or_else(|_| Date::parse(&s, &FORMAT).map(Timestamp::Date))
+            .map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "invalid date {}, expected dd-mm-yyyy or RFC 3339",
+                    s
+                ))
+            })
+    }
+}
 
 /// A record of a job application
-#[serde_as]
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct Record {
     /// the last time any action happened to this job we push new strings to the back!
-    #[serde_as(as = "Vec<FromInto<MyDate>>")]
-    last_action_date: Vec<Date>,
+    last_action_date: Vec<Timestamp>,
     /// the name of the company
     pub(crate) name: String,
     /// the job name
     pub(crate) subname: String,
     /// at what stage are we, i.e., first interview, second and so on
     pub(crate) stage: String,
+    /// the stages we went through, the last one is the current one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) stages: Vec<Stage>,
     /// some additional information we want to store
     pub(crate) additional_info: String,
     /// the status of the job
     pub(crate) status: Status,
     /// where
     pub(crate) place: String,
+    /// every status change with why we did it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) transitions: Vec<Transition>,
+    /// why we got rejected, if we know it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) rejection: Option<Rejection>,
+    /// the details of the offer we got
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) offer: Option<Offer>,
+    /// fields we do not know about, i.e., written by a newer version, we keep them unchanged
+    #[serde(flatten)]
+    pub(crate) extra: Map<String, Value>,
+}
+
+/// Why a job application got rejected
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct Rejection {
+    /// the reason out of the configured ones
+    pub(crate) reason: Option<String>,
+    /// the stage we were at when we got rejected
+    pub(crate) stage: String,
+    /// anything else we want to remember
+    pub(crate) comment: String,
+}
+
+/// A change of the status
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct Transition {
+    /// when we changed it
+    pub(crate) date: Timestamp,
+    /// the status before
+    pub(crate) from: Status,
+    /// the status after
+    pub(crate) to: Status,
+    /// why we changed it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) comment: String,
+}
+
+/// A stage of the interview pipeline
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct Stage {
+    /// the name out of the configured template
+    pub(crate) name: String,
+    /// anything we want to add, i.e., "with the CTO"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) label: String,
+    /// when it happened
+    #[serde(with = "day_format")]
+    pub(crate) date: Date,
+    /// how it went
+    #[serde(default)]
+    pub(crate) result: StageResult,
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)?;
+        if !self.label.is_empty() {
+            write!(f, " ({})", self.label)?;
+        }
+        Ok(())
+    }
+}
+
+/// How a stage went
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Deserialize, Serialize, clap::ValueEnum)]
+pub(crate) enum StageResult {
+    /// we do not know yet
+    #[default]
+    Pending,
+    /// we made it to the next stage
+    Passed,
+    /// it ended here
+    Failed,
+}
+
+impl std::fmt::Display for StageResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            StageResult::Pending => "pending",
+            StageResult::Passed => "passed",
+            StageResult::Failed => "failed",
+        })
+    }
+}
+
+/// The details of an offer, amounts are yearly and in whatever currency we like
+#[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub(crate) struct Offer {
+    /// the base salary
+    pub(crate) base_salary: Option<u64>,
+    /// the expected bonus
+    pub(crate) bonus: Option<u64>,
+    /// the value of the equity
+    pub(crate) equity: Option<u64>,
+    /// everything else, i.e., vacation days or remote work
+    pub(crate) benefits: String,
+    /// when we would start
+    #[serde(with = "day_format::option")]
+    pub(crate) start_date: Option<Date>,
+    /// until when we have to decide
+    #[serde(with = "day_format::option")]
+    pub(crate) deadline: Option<Date>,
+}
+
+impl Offer {
+    /// the sum of all amounts we know, huge amounts stop at the largest one we can store
+    pub(crate) fn total(&self) -> u64 {
+        [self.base_salary, self.bonus, self.equity]
+            .into_iter()
+            .flatten()
+            .fold(0, u64::saturating_add)
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.reason.as_deref().unwrap_or("no reason"))?;
+        if !self.stage.is_empty() {
+            write!(f, " at {}", self.stage)?;
+        }
+        if !self.comment.is_empty() {
+            write!(f, ": {}", self.comment)?;
+        }
+        Ok(())
+    }
 }
 
 impl PartialOrd for Record {
@@ -66,7 +270,7 @@ impl Ord for Record {
         } else if self.status != Status::Todo && other.status == Status::Todo {
             Ordering::Greater
         } else {
-            self.get_date().cmp(other.get_date()).reverse()
+            self.last_action().cmp(&other.last_action()).reverse()
         }
     }
 }
@@ -80,40 +284,82 @@ impl Record {
             stage: String::new(),
             additional_info: String::new(),
             status: Status::Todo,
-            last_action_date: vec![OffsetDateTime::now_local().expect("Error in getting time").date()],
+            last_action_date: vec![Timestamp::now()],
             place,
+            stages: Vec::new(),
+            transitions: Vec::new(),
+            rejection: None,
+            offer: None,
+            extra: Map::new(),
         }
     }
 
     /// update the last action date
     pub(crate) fn update_date(&mut self) {
-        let now = OffsetDateTime::now_local().expect("Error in getting time").date();
-        if self.last_action_date.last().map(|d| *d!=now).unwrap_or(true) {
-            self.last_action_date.push(now);
-        }
+        self.last_action_date.push(Timestamp::now());
     }
 
-    /// returns the date
-    pub(crate) fn get_date(&self) -> &Date {
-        self.last_action_date.last().unwrap()
+    /// all times we had an action, oldest first
+    pub(crate) fn dates(&self) -> &[Timestamp] {
+        &self.last_action_date
     }
 
-    /// returns the last date we had an action formatted
-    pub(crate) fn date_string(&self) -> String {
-        let d = self.last_action_date.last().unwrap();
-        d.format(&FORMAT).unwrap()
+    /// mutable access to the action times, only used to repair broken records and to backdate new ones
+    pub(crate) fn dates_mut(&mut self) -> &mut Vec<Timestamp> {
+        &mut self.last_action_date
     }
 
-    /// toggle stage
-    pub(crate) fn next_stage(&mut self) {
-        self.status = self.status.next();
-        self.update_date();
+    /// returns the time of the last action
+    pub(crate) fn last_action(&self) -> Timestamp {
+        *self.last_action_date.last().unwrap()
     }
 
-    /// sets the status
+    /// returns the date
+    pub(crate) fn get_date(&self) -> Date {
+        self.last_action().date()
+    }
+
+    /// returns the last date we had an action formatted, with the time if `show_time` is set
+    pub(crate) fn date_string(&self, show_time: bool) -> String {
+        self.last_action().format(show_time)
+    }
+
+    /// sets the status and remembers the change, nothing happens if it is the same.
+    /// Leaving rejected drops the rejection, its comment stays in the transitions
     pub(crate) fn set_status(&mut self, status: Status) {
+        let from = self.status;
+        if from == status {
+            return;
+        }
         self.status = status;
+        if status != Status::Rejected {
+            self.rejection = None;
+        }
         self.update_date();
+        self.transitions.push(Transition {
+            date: self.last_action(),
+            from,
+            to: status,
+            comment: String::new(),
+        });
+    }
+
+    /// explain the last status change
+    pub(crate) fn comment_transition(&mut self, comment: String) {
+        if let Some(t) = self.transitions.last_mut() {
+            t.comment = comment;
+        }
+    }
+
+    /// remember why we got rejected at the current stage
+    pub(crate) fn set_rejection(&mut self, reason: Option<String>, comment: String) {
+        // the comment explains the rejection as well
+        self.comment_transition(comment.clone());
+        self.rejection = Some(Rejection {
+            reason,
+            stage: self.stage.clone(),
+            comment,
+        });
     }
 
     /// sets the stage of the job
@@ -122,90 +368,178 @@ impl Record {
         self.update_date();
     }
 
-    /// test if the job is old, i.e., 2 weeks after last action date
+    /// start the next stage of the pipeline today, the stage text shows it from now on
+    pub(crate) fn add_stage(&mut self, name: String, label: String) {
+        let stage = Stage {
+            name,
+            label,
+            date: clock::today(),
+            result: StageResult::Pending,
+        };
+        self.stage = stage.to_string();
+        self.stages.push(stage);
+        self.update_date();
+    }
+
+    /// set how the current stage went, `false` if we do not have one
+    pub(crate) fn set_stage_result(&mut self, result: StageResult) -> bool {
+        match self.stages.last_mut() {
+            Some(stage) => {
+                stage.result = result;
+                self.update_date();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// test if the job is old according to the staleness rules in the config
     pub(crate) fn is_old(&self) -> bool {
-        let today = OffsetDateTime::now_local().expect("Error in getting time").date();
-        self.status != Status::Todo && today - *self.get_date() >= Duration::weeks(2)
-    }
-
-    /// print one entry
-    pub(crate) fn print(&self, index: usize, truncate: bool) -> anyhow::Result<()> {
-        let date = self.date_string();
-        if truncate && self.is_old() {
-            println!(
-                "{:2} | {:-^10} | {:-^20} | {:-^20} | {:^37} | {:^30} | {}",
-                index.dim(),
-                self.status.print().dim(),
-                date.dim(),
-                self.name.bold().dim(),
-                self.subname.bold().dim(),
-                self.stage.dim(),
-                self.place,
-            );
-        } else if truncate {
-            println!(
-                "{:2} | {:-^10} | {:-^20} | {:-^20} | {:^37} | {:^30} | {}",
-                index,
-                self.status.print(),
-                date,
-                self.name.bold(),
-                self.subname.bold(),
-                self.stage,
-                self.place,
-            );
-        } else {
-            println!(
-                "{:2} | {:-^10} | {:-^20} | {:-^20} | {:^37} | {:^30} | {} | {}",
-                index,
-                self.status.print(),
-                date,
-                self.name.bold(),
-                self.subname.bold(),
-                self.stage,
-                self.additional_info,
-                self.place,
-            );
+        staleness::is_stale(self, clock::today())
+    }
+}
+
+#[cfg(test)]
+impl Record {
+    /// a pending backend job at `name` in Berlin with actions at `dates`, for tests
+    pub(crate) fn test(name: &str, dates: &[&str]) -> Self {
+        Record {
+            name: name.to_string(),
+            subname: "Backend".to_string(),
+            status: Status::Pending,
+            last_action_date: dates
+                .iter()
+                .map(|d| serde_json::from_value(Value::from(*d)).unwrap())
+                .collect(),
+            ..Record::new(String::new(), String::new(), "Berlin".to_string())
         }
-        Ok(())
+    }
+
+    /// the record with `status`
+    pub(crate) fn with_status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// the record with the job `subname`
+    pub(crate) fn with_subname(mut self, subname: &str) -> Self {
+        self.subname = subname.to_string();
+        self
+    }
+
+    /// the record with `stage`
+    pub(crate) fn with_stage(mut self, stage: &str) -> Self {
+        self.stage = stage.to_string();
+        self
+    }
+
+    /// the record in `place`
+    pub(crate) fn with_place(mut self, place: &str) -> Self {
+        self.place = place.to_string();
+        self
     }
 }
 
-#[derive(Serialize, Deserialize)]
-pub(crate) struct Records(
-    pub(crate) Vec<Record>);
+/// the group we show a record in, closed ones first, then open ones and todo last
+fn group(r: &Record) -> u8 {
+    if !r.status.is_open() {
+        0
+    } else if r.status != Status::Todo {
+        1
+    } else {
+        2
+    }
+}
+
+/// All records, the second field holds the top level fields if the file is an object instead of a plain list
+pub(crate) struct Records(pub(crate) Vec<Record>, Option<Map<String, Value>>);
+
+/// The key under which the records are stored if the file is an object
+const RECORDS_KEY: &str = "Records";
+
+impl From<Vec<Record>> for Records {
+    fn from(value: Vec<Record>) -> Self {
+        Records(value, None)
+    }
+}
 
 impl Records {
     /// load records
     pub(crate) fn load() -> anyhow::Result<Self> {
-        let f = File::open(PATH.clone())?;
+        Records::load_from(&PATH)
+    }
+
+    /// load records from the given file
+    pub(crate) fn load_from(path: &Path) -> anyhow::Result<Self> {
+        let f = File::open(path)?;
         let br = BufReader::new(f);
-        let d = &mut serde_json::Deserializer::from_reader(br);
+        let value: Value = serde_json::from_reader(br)?;
+        let (records, extra) = match value {
+            Value::Object(mut map) => {
+                // we leave a null in place so the order of the fields stays the same when writing
+                let records = map
+                    .get_mut(RECORDS_KEY)
+                    .map(Value::take)
+                    .ok_or_else(|| anyhow!("Error in parsing: no {} field", RECORDS_KEY))?;
+                (records, Some(map))
+            }
+            v => (v, None),
+        };
 
-        let result: Result<Vec<Record>, _> = serde_path_to_error::deserialize(d);
+        let result: Result<Vec<Record>, _> = serde_path_to_error::deserialize(records);
         if let Err(e) = result {
             Err(anyhow!("Error in parsing {}", e))
         } else {
-
-            let rdr = result.unwrap();
-            let rej = rdr
-            .iter()
-            .filter(|a| a.status == Status::Declined || a.status == Status::Rejected);
-        let pen = rdr.iter().filter(|a| a.status == Status::Pending);
-        let todo = rdr.iter().filter(|a| a.status == Status::Todo);
-        Ok(Records(
-            rej.chain(pen).chain(todo).cloned().collect::<Vec<Record>>(),
-        ))
+            let mut rdr = result.unwrap();
+            // the sort is stable, so the file order stays within a group
+            rdr.sort_by_key(group);
+            Ok(Records(rdr, extra))
+        }
     }
+
+    /// the last modification time of the data file
+    pub(crate) fn modified() -> Option<SystemTime> {
+        std::fs::metadata(PATH.clone())
+            .and_then(|m| m.modified())
+            .ok()
     }
 
     /// write records to file
     pub(crate) fn write(&self) -> anyhow::Result<()> {
-        let f = File::create(PATH.clone())?;
+        self.write_to(&PATH)
+    }
+
+    /// write records to the given file
+    pub(crate) fn write_to(&self, path: &Path) -> anyhow::Result<()> {
+        let f = File::create(path)?;
         let br = BufWriter::new(f);
-        serde_json::to_writer_pretty(br, self)?;
+        if let Some(extra) = &self.1 {
+            let mut map = extra.clone();
+            map.insert(RECORDS_KEY.to_string(), serde_json::to_value(&self.0)?);
+            serde_json::to_writer_pretty(br, &map)?;
+        } else {
+            serde_json::to_writer_pretty(br, &self.0)?;
+        }
         Ok(())
     }
 
+    /// only keep what we knew at the end of `date`, i.e., later actions and records are dropped
+    /// and the status is the one before the first later change
+    pub(crate) fn as_of(&mut self, date: Date) {
+        self.0.retain_mut(|r| {
+            if let Some(later) = r.transitions.iter().find(|t| t.date.date() > date) {
+                r.status = later.from;
+                if r.status != Status::Rejected {
+                    r.rejection = None;
+                }
+            }
+            r.last_action_date.retain(|d| d.date() <= date);
+            r.transitions.retain(|t| t.date.date() <= date);
+            r.stages.retain(|s| s.date <= date);
+            !r.last_action_date.is_empty()
+        });
+    }
+
     pub(crate) fn get(&self, index: usize) -> Option<&Record> {
         self.0.get(index)
     }
@@ -214,6 +548,23 @@ impl Records {
         self.0.get_mut(index)
     }
 
+    /// add a record where loading the file would put it, so its index stays the same
+    pub(crate) fn add(&mut self, record: Record) -> usize {
+        let index = self.0.iter().filter(|r| group(r) <= group(&record)).count();
+        self.0.insert(index, record);
+        index
+    }
+
+    /// the record at `index` or an error if there is none
+    pub(crate) fn find(&self, index: usize) -> Result<&Record, NotFound> {
+        self.get(index).ok_or(NotFound(index))
+    }
+
+    /// the record at `index` to change or an error if there is none
+    pub(crate) fn find_mut(&mut self, index: usize) -> Result<&mut Record, NotFound> {
+        self.get_mut(index).ok_or(NotFound(index))
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.0.len()
     }
@@ -222,3 +573,94 @@ impl Records {
         self.0.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::macros::{date, datetime};
+
+    use super::*;
+
+    /// a record with the given dates and transitions
+    fn record(status: Status, dates: &[&str], transitions: Value) -> Record {
+        let mut r = Record::test("Acme", dates).with_status(status);
+        r.transitions = serde_json::from_value(transitions).unwrap();
+        r
+    }
+
+    #[test]
+    fn timestamps_read_the_old_and_the_new_format() {
+        let old: Timestamp = serde_json::from_value(json!("01-09-2026")).unwrap();
+        assert!(matches!(old, Timestamp::Date(d) if d == date!(2026 - 09 - 01)));
+        let new: Timestamp = serde_json::from_value(json!("2026-09-01T10:30:00+02:00")).unwrap();
+        assert!(matches!(new, Timestamp::DateTime(t) if t == datetime!(2026-09-01 10:30 +2)));
+        assert!(serde_json::from_value::<Timestamp>(json!("2026/09/01")).is_err());
+    }
+
+    #[test]
+    fn timestamps_write_the_format_they_were_read_in() {
+        for s in ["01-09-2026", "2026-09-01T10:30:00+02:00"] {
+            let t: Timestamp = serde_json::from_value(json!(s)).unwrap();
+            assert_eq!(serde_json::to_value(t).unwrap(), json!(s));
+        }
+    }
+
+    #[test]
+    fn timestamps_without_time_come_first_on_their_day() {
+        let day = Timestamp::Date(date!(2026 - 09 - 01));
+        let morning = Timestamp::DateTime(datetime!(2026-09-01 08:00 UTC));
+        let evening = Timestamp::DateTime(datetime!(2026-09-01 20:00 UTC));
+        let next_day = Timestamp::Date(date!(2026 - 09 - 02));
+        let mut dates = vec![next_day, evening, day, morning];
+        dates.sort();
+        assert_eq!(dates, [day, morning, evening, next_day]);
+        assert_eq!(next_day.date(), date!(2026 - 09 - 02));
+    }
+
+    #[test]
+    fn as_of_drops_later_actions_and_rewinds_the_status() {
+        let transitions = json!([
+            {"Date": "2026-09-10T10:00:00Z", "From": "Todo", "To": "Pending"},
+            {"Date": "2026-10-01T10:00:00Z", "From": "Pending", "To": "Withdrawn"},
+        ]);
+        let r = record(
+            Status::Withdrawn,
+            &["01-09-2026", "2026-09-10T10:00:00Z", "2026-10-01T10:00:00Z"],
+            transitions,
+        );
+        let later = record(Status::Todo, &["05-10-2026"], json!([]));
+        let mut rdr = Records::from(vec![r, later]);
+        rdr.as_of(date!(2026 - 09 - 15));
+        assert_eq!(rdr.len(), 1);
+        let r = rdr.get(0).unwrap();
+        assert_eq!(r.status, Status::Pending);
+        assert_eq!(r.transitions.len(), 1);
+        assert_eq!(r.get_date(), date!(2026 - 09 - 10));
+
+        let mut rdr = Records::from(vec![r.clone()]);
+        rdr.as_of(date!(2026 - 09 - 05));
+        assert_eq!(rdr.get(0).unwrap().status, Status::Todo);
+    }
+
+    #[test]
+    fn set_status_to_the_same_status_changes_nothing() {
+        let mut r = record(Status::Rejected, &["01-09-2026"], json!([]));
+        let before = r.clone();
+        r.set_status(Status::Rejected);
+        assert_eq!(r, before);
+        r.set_status(Status::Todo);
+        assert_eq!(r.transitions.len(), 1);
+        assert_eq!(r.last_action().date(), clock::today());
+    }
+
+    #[test]
+    fn leaving_rejected_drops_the_rejection() {
+        let mut r = Record::test("Acme", &["01-09-2026"]).with_stage("first interview");
+        r.set_status(Status::Rejected);
+        r.set_rejection(Some("Salary".to_string()), "too expensive".to_string());
+        assert_eq!(r.rejection.as_ref().unwrap().stage, "first interview");
+        r.set_status(Status::Interviewing);
+        assert_eq!(r.rejection, None);
+        assert_eq!(r.transitions[0].comment, "too expensive");
+    }
+}
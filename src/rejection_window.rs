@@ -0,0 +1,78 @@
+use ratatui::{
+    crossterm::event::{self, KeyCode},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Clear, List, ListState, Paragraph},
+    Frame,
+};
+
+use crate::{
+    config,
+    types::{center, GuiState, WindowFocus},
+};
+
+/// draw the rejection reason popup
+pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
+    if let WindowFocus::Rejection(selected, ref comment, _) = state.focus {
+        let reasons = &config::get().rejection.reasons;
+        let area = center(
+            frame.area(),
+            Constraint::Percentage(30),
+            Constraint::Length(reasons.len() as u16 + 5), // borders of both blocks + comment line
+        );
+        let l = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(area);
+        let list = List::new(reasons.iter().map(String::as_str))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol(">>")
+            .block(Block::bordered().title("Why did we get rejected (Esc to skip)"));
+        let text = Paragraph::new(comment.as_str()).block(Block::bordered().title("Comment"));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(
+            list,
+            l[0],
+            &mut ListState::default().with_selected(Some(selected)),
+        );
+        frame.render_widget(text, l[1]);
+        frame.set_cursor_position(Position::new(
+            l[1].x + comment.len() as u16 + 1,
+            // Move one line down, from the border to the input line
+            l[1].y + 1,
+        ))
+    }
+}
+
+/// handle inputs for the rejection reason popup
+pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
+    let WindowFocus::Rejection(ref mut selected, ref mut comment, real_index) = state.focus else {
+        return;
+    };
+    let reasons = &config::get().rejection.reasons;
+    match key.code {
+        KeyCode::Esc => {
+            state.focus = WindowFocus::Table;
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(reasons.len().saturating_sub(1));
+        }
+        KeyCode::Char(c) => {
+            comment.push(c);
+        }
+        KeyCode::Backspace => {
+            comment.pop();
+        }
+        KeyCode::Enter => {
+            let reason = reasons.get(*selected).cloned();
+            let comment = std::mem::take(comment);
+            state
+                .rdr
+                .get_mut(real_index)
+                .unwrap()
+                .set_rejection(reason, comment);
+            state.focus = WindowFocus::Table;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    crossterm::event::{self, KeyCode},
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    merge::{Merge, Side},
+    records::Records,
+    types::{center, GuiState, WindowFocus},
+};
+
+/// how often we look at the data file
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// check if the data file changed on disk, reload it if we have no local changes or ask otherwise
+pub(crate) fn check(state: &mut GuiState) {
+    if state.last_disk_check.elapsed() < CHECK_INTERVAL
+        || !matches!(state.focus, WindowFocus::Table | WindowFocus::Search)
+    {
+        return;
+    }
+    state.last_disk_check = Instant::now();
+    let modified = Records::modified();
+    if modified == state.disk_modified {
+        return;
+    }
+    if state.has_local_changes() {
+        state.focus = WindowFocus::Reload;
+    } else if let Ok(rdr) = Records::load() {
+        // if the file does not parse it is probably still being written, so we try again later
+        state.disk_modified = modified;
+        state.replace_records(rdr);
+        state.disk_records = state.rdr.0.clone();
+    }
+}
+
+/// load the file from disk and remember it as the current disk state
+fn load(state: &mut GuiState) -> Option<Records> {
+    let modified = Records::modified();
+    let mut rdr = Records::load().ok()?;
+    rdr.0.sort_unstable();
+    state.disk_modified = modified;
+    state.disk_records = rdr.0.clone();
+    Some(rdr)
+}
+
+/// draw the reload prompt
+pub(crate) fn draw(frame: &mut Frame, _: Rect, _: &GuiState) {
+    let area = center(
+        frame.area(),
+        Constraint::Percentage(40),
+        Constraint::Length(7), // top and bottom border + content
+    );
+    let key = |k| Span::styled(k, Style::default().fg(Color::Green));
+    let text = Paragraph::new(vec![
+        Line::from("The data file changed on disk but we have unsaved changes."),
+        Line::from(""),
+        Line::from(vec![key("r"), Span::from(": reload and drop our changes")]),
+        Line::from(vec![key("k"), Span::from(": keep our changes")]),
+        Line::from(vec![
+            key("m"),
+            Span::from(": merge, keeping our version on conflicts"),
+        ]),
+    ])
+    .block(Block::bordered().title("Reload"));
+    frame.render_widget(Clear, area);
+    frame.render_widget(text, area);
+}
+
+/// reload window input handler
+pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
+    match key.code {
+        KeyCode::Char('r') => {
+            if let Some(rdr) = load(state) {
+                state.replace_records(rdr);
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Esc => {
+            load(state);
+        }
+        KeyCode::Char('m') => {
+            let base = state.disk_records.clone();
+            if let Some(mut rdr) = load(state) {
+                let mut merge = Merge::new(Some(&base), &state.rdr.0, &rdr.0);
+                for conflict in merge.conflicts.clone() {
+                    for field in &conflict.fields {
+                        merge.resolve(&conflict, field, Side::Ours);
+                    }
+                }
+                if let Ok(merged) = merge.finish() {
+                    rdr.0 = merged;
+                    state.replace_records(rdr);
+                }
+            }
+        }
+        _ => return,
+    }
+    state.focus = WindowFocus::Table;
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use yansi::Paint;
+
+use crate::{
+    config,
+    output::{self, Format},
+    records::{Records, StageResult},
+    stages,
+    types::Status,
+};
+
+/// print one table of counts with percentages
+fn print_counts(title: &str, counts: &BTreeMap<String, usize>, total: usize) {
+    println!("{}", title.underline());
+    let mut counts = counts.iter().collect::<Vec<(&String, &usize)>>();
+    counts.sort_by(|a, b| b.1.cmp(a.1));
+    for (key, val) in counts {
+        let percentage = (*val as f64) / (total as f64) * 100_f64;
+        println!("{:>30} | {:3} ({:.1}%)", key, val, percentage);
+    }
+}
+
+/// The fields of a stage of the funnel in the other formats
+const FUNNEL_FIELDS: [&str; 5] = ["Stage", "Reached", "Passed", "Failed", "Applications"];
+
+/// print how many applications reached each stage of the template in `format`
+pub(crate) fn funnel(rdr: &Records, format: Format) -> anyhow::Result<()> {
+    let template = &config::get().stages.template;
+    let applications = rdr.iter().filter(|r| r.status != Status::Todo).count();
+    let mut reached = vec![0; template.len()];
+    let mut passed = vec![0; template.len()];
+    let mut failed = vec![0; template.len()];
+    for r in rdr.iter() {
+        let positions = r
+            .stages
+            .iter()
+            .filter_map(|s| stages::position(&s.name).map(|p| (p, s.result)))
+            .collect::<Vec<_>>();
+        // reaching a stage means we got through all stages before it
+        if let Some(furthest) = positions.iter().map(|(p, _)| *p).max() {
+            for count in reached.iter_mut().take(furthest + 1) {
+                *count += 1;
+            }
+        }
+        for (p, result) in positions {
+            match result {
+                StageResult::Passed => passed[p] += 1,
+                StageResult::Failed => failed[p] += 1,
+                StageResult::Pending => {}
+            }
+        }
+    }
+
+    if format != Format::Table {
+        let maps = template
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                output::row(
+                    FUNNEL_FIELDS,
+                    [
+                        Value::from(name.as_str()),
+                        Value::from(reached[i]),
+                        Value::from(passed[i]),
+                        Value::from(failed[i]),
+                        Value::from(applications),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>();
+        return output::write_maps(format, &FUNNEL_FIELDS, &maps);
+    }
+
+    println!("-------------------FUNNEL---------------------------------------");
+    println!(
+        "{:>20} | {:>7} | {:>8} | {:>8} | {:>6} | {:>6}",
+        "Stage", "Reached", "of all", "of prev", "passed", "failed"
+    );
+    println!("{:>20} | {:7} |", "Applications", applications);
+    let mut previous = applications;
+    for (i, name) in template.iter().enumerate() {
+        let percent = |of: usize| {
+            if of == 0 {
+                "-".to_string()
+            } else {
+                format!("{:.1}%", reached[i] as f64 / of as f64 * 100_f64)
+            }
+        };
+        println!(
+            "{:>20} | {:7} | {:>8} | {:>8} | {:6} | {:6}",
+            name,
+            reached[i],
+            percent(applications),
+            percent(previous),
+            passed[i],
+            failed[i]
+        );
+        previous = reached[i];
+    }
+    println!("----------------------------------------------------------------");
+    Ok(())
+}
+
+/// The fields of a rejection in the other formats
+const REJECTION_FIELDS: [&str; 6] = ["Index", "Name", "Subname", "Reason", "Stage", "Comment"];
+
+/// print the rejections broken down by reason and by the stage they happened at,
+/// the other formats write every rejection for the scripts to count
+pub(crate) fn rejections(rdr: &Records, format: Format) -> anyhow::Result<()> {
+    let rejected = rdr
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.status == Status::Rejected)
+        .collect::<Vec<_>>();
+    if format != Format::Table {
+        let maps = rejected
+            .iter()
+            .map(|(i, r)| {
+                let rejection = r.rejection.as_ref();
+                output::row(
+                    REJECTION_FIELDS,
+                    [
+                        Value::from(*i),
+                        Value::from(r.name.as_str()),
+                        Value::from(r.subname.as_str()),
+                        Value::from(rejection.and_then(|rej| rej.reason.clone())),
+                        Value::from(rejection.map_or(r.stage.as_str(), |rej| rej.stage.as_str())),
+                        Value::from(rejection.map_or("", |rej| rej.comment.as_str())),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>();
+        return output::write_maps(format, &REJECTION_FIELDS, &maps);
+    }
+    let rejected = rejected.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
+    let total = rejected.len();
+    println!("-------------------REJECTIONS-----------------------------------");
+    println!("{} rejections", total);
+    if total == 0 {
+        return Ok(());
+    }
+
+    let mut by_reason = BTreeMap::new();
+    let mut by_stage = BTreeMap::new();
+    for r in &rejected {
+        let (reason, stage) = match &r.rejection {
+            Some(rej) => (
+                rej.reason
+                    .clone()
+                    .unwrap_or_else(|| "<no reason>".to_string()),
+                rej.stage.clone(),
+            ),
+            None => ("<not recorded>".to_string(), r.stage.clone()),
+        };
+        let stage = if stage.trim().is_empty() {
+            "<no stage>".to_string()
+        } else {
+            stage
+        };
+        *by_reason.entry(reason).or_insert(0) += 1;
+        *by_stage.entry(stage).or_insert(0) += 1;
+    }
+    print_counts("By reason", &by_reason, total);
+    print_counts("By stage", &by_stage, total);
+
+    let comments = rejected
+        .iter()
+        .filter_map(|r| r.rejection.as_ref().map(|rej| (r, rej)))
+        .filter(|(_, rej)| !rej.comment.is_empty())
+        .collect::<Vec<_>>();
+    if !comments.is_empty() {
+        println!("{}", "Comments".underline());
+        for (r, rej) in comments {
+            println!("{} | {}: {}", r.name.bold(), r.subname.bold(), rej.comment);
+        }
+    }
+    println!("----------------------------------------------------------------");
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use ratatui::{
     crossterm::event::{self, KeyCode},
     layout::{Position, Rect},
-    style::{Color, Style},
+    style::{Color, Style, Stylize},
+    text::Line,
     widgets::{Block, Paragraph},
     Frame,
 };
@@ -19,10 +20,19 @@ pub(crate) fn draw(frame: &mut Frame, r: Rect, state: &GuiState) {
             state.search.clone().unwrap_or_default(),
         )
     } else {
-        (Style::default(), "search".to_string())
+        (
+            Style::default(),
+            "search, i.e., status:pending place:berlin since:2026-09-01 \"backend\"".to_string(),
+        )
     };
     let length = txt.len();
-    let input = Paragraph::new(txt).style(style).block(Block::bordered());
+    let mode = format!("{:?}", state.search_mode).to_lowercase();
+    let block = match &state.query_error {
+        Some(e) => Block::bordered().title(e.as_str()).red(),
+        None => Block::bordered(),
+    }
+    .title_top(Line::from(mode).right_aligned());
+    let input = Paragraph::new(txt).style(style).block(block);
     frame.render_widget(input, r);
     if state.focus == WindowFocus::Search {
         frame.set_cursor_position(Position::new(
@@ -38,6 +48,7 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
     match key.code {
         KeyCode::Esc => {
             state.search.take();
+            state.update_query();
             state.focus = WindowFocus::Table;
         }
         KeyCode::Char(k) => {
@@ -46,13 +57,19 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
             } else {
                 state.search = Some(k.to_string());
             }
+            state.update_query();
+        }
+        KeyCode::Tab => {
+            state.search_mode = state.search_mode.next();
+            state.update_query();
         }
         KeyCode::Backspace => {
             state.search.as_mut().unwrap().pop();
+            state.update_query();
         }
         _ => {
             // we still want normal stuff
-            table_window::handle_input(key, state);
+            let _ = table_window::handle_input(key, state);
         }
     }
 }
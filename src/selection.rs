@@ -0,0 +1,308 @@
+use std::ops::RangeInclusive;
+
+use inquire::{InquireError, Select};
+use yansi::Paint;
+
+use crate::{
+    exit::{self, NotFound},
+    prompt,
+    query::{self, Query, SearchMode},
+    records::{Record, Records},
+};
+
+/// The jobs a command works on
+#[derive(Clone, Debug)]
+pub(crate) enum Selection {
+    /// indices and ranges like `3-7,12`, a single index is a range as well.
+    /// We expand them when we know how many jobs there are
+    Indices(Vec<RangeInclusive<usize>>),
+    /// a single job whose company or job name contains the text, ignoring case
+    Name(String),
+    /// all jobs matching a query like `status:pending stale:yes`, closed ones as well.
+    /// We parse it when we use it, the status names come from the config
+    Query(String),
+}
+
+/// parse indices and ranges like `3-7,12`, a query if it uses a filter and a name otherwise
+pub(crate) fn parse(s: &str) -> Result<Selection, String> {
+    if !s
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ',' | '-' | ' '))
+    {
+        return Ok(if query::has_filter(s) {
+            Selection::Query(s.to_string())
+        } else {
+            Selection::Name(s.trim().trim_matches('"').to_string())
+        });
+    }
+    let mut ranges = Vec::new();
+    for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let number = |n: &str| {
+            n.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("{} is not an index or a range like 3-7", part))
+        };
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (number(from)?, number(to)?);
+                if from > to {
+                    return Err(format!("{} is an empty range", part));
+                }
+                ranges.push(from..=to);
+            }
+            None => {
+                let index = number(part)?;
+                ranges.push(index..=index);
+            }
+        }
+    }
+    if ranges.is_empty() {
+        return Err("no index given".to_string());
+    }
+    Ok(Selection::Indices(ranges))
+}
+
+impl Selection {
+    /// the indices of the selected jobs, an error if one of the given indices does not exist
+    fn indices(&self, rdr: &Records) -> anyhow::Result<Vec<usize>> {
+        match self {
+            Selection::Indices(ranges) => {
+                // the first index that does not exist, a range may start behind the last job
+                if let Some(r) = ranges.iter().find(|r| *r.end() >= rdr.len()) {
+                    return Err(NotFound(*r.start().max(&rdr.len())).into());
+                }
+                let mut indices = ranges.iter().cloned().flatten().collect::<Vec<_>>();
+                indices.sort();
+                indices.dedup();
+                Ok(indices)
+            }
+            Selection::Name(name) => {
+                let name = name.to_lowercase();
+                Ok(rdr
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| {
+                        [&r.name, &r.subname, &format!("{} {}", r.name, r.subname)]
+                            .iter()
+                            .any(|f| f.to_lowercase().contains(&name))
+                    })
+                    .map(|(i, _)| i)
+                    .collect())
+            }
+            Selection::Query(query) => {
+                let query = Query::parse(query, SearchMode::Plain).map_err(exit::invalid)?;
+                Ok(rdr
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| query.matches(r))
+                    .map(|(i, _)| i)
+                    .collect())
+            }
+        }
+    }
+}
+
+/// let the user pick one of the `candidates` with a fuzzy search, `None` if they give up
+fn pick(rdr: &Records, candidates: &[usize]) -> anyhow::Result<Option<usize>> {
+    if candidates.is_empty() {
+        println!("There is no job to pick from");
+        return Ok(None);
+    }
+    if !prompt::terminal() {
+        return Err(exit::invalid(
+            "Give the job as an index or a name, we can only ask in a terminal",
+        ));
+    }
+    let options = candidates
+        .iter()
+        .map(|i| {
+            let r = rdr.get(*i).unwrap();
+            format!("{:2} | {} | {} | {}", i, r.name, r.subname, r.status.name())
+        })
+        .collect::<Vec<_>>();
+    match Select::new("Which job (type to search)", options).raw_prompt() {
+        Ok(ans) => Ok(Some(candidates[ans.index])),
+        Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// the jobs the `candidate` filter lets through
+fn candidates(rdr: &Records, candidate: impl Fn(&Record) -> bool) -> Vec<usize> {
+    rdr.iter()
+        .enumerate()
+        .filter(|(_, r)| candidate(r))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// the indices of the given jobs, without any we let the user pick one of the `candidate` jobs.
+/// A name is a single job, we let the user pick one if several match
+pub(crate) fn indices(
+    jobs: Option<&Selection>,
+    rdr: &Records,
+    candidate: impl Fn(&Record) -> bool,
+) -> anyhow::Result<Vec<usize>> {
+    let indices = match jobs {
+        Some(Selection::Name(_)) => return Ok(single(jobs, rdr, candidate)?.into_iter().collect()),
+        Some(jobs) => jobs.indices(rdr)?,
+        None => pick(rdr, &candidates(rdr, candidate))?
+            .into_iter()
+            .collect(),
+    };
+    if indices.is_empty() && jobs.is_some() {
+        println!("No job matches the query");
+    }
+    Ok(indices)
+}
+
+/// a single job, if the given jobs are more than one we let the user pick one of them.
+/// A name only matches the `candidate` jobs
+pub(crate) fn single(
+    jobs: Option<&Selection>,
+    rdr: &Records,
+    candidate: impl Fn(&Record) -> bool,
+) -> anyhow::Result<Option<usize>> {
+    let indices = match jobs {
+        Some(name @ Selection::Name(_)) => name
+            .indices(rdr)?
+            .into_iter()
+            .filter(|i| candidate(rdr.get(*i).unwrap()))
+            .collect(),
+        Some(jobs) => jobs.indices(rdr)?,
+        None => candidates(rdr, candidate),
+    };
+    match indices[..] {
+        [index] if jobs.is_some() => Ok(Some(index)),
+        [] if jobs.is_some() => {
+            println!("No job matches the query");
+            Ok(None)
+        }
+        _ if jobs.is_some() && !prompt::terminal() => Err(exit::invalid(format!(
+            "{} jobs match, give the one you mean as an index",
+            indices.len()
+        ))),
+        _ => pick(rdr, &indices),
+    }
+}
+
+/// ask once if we should `action` all jobs, i.e., "change", listing them if there is more than one
+pub(crate) fn confirm(
+    rdr: &Records,
+    indices: &[usize],
+    action: &str,
+    change: &str,
+) -> anyhow::Result<bool> {
+    if let [index] = indices {
+        let r = rdr.get(*index).unwrap();
+        return prompt::confirm(&format!(
+            "Do you want to {} {} | {}{}",
+            action, r.name, r.subname, change
+        ));
+    }
+    for i in indices {
+        let r = rdr.get(*i).unwrap();
+        println!(
+            "{:2} | {} | {} | {}",
+            i,
+            r.name.bold(),
+            r.subname.bold(),
+            r.status.print()
+        );
+    }
+    prompt::confirm(&format!(
+        "Do you want to {} these {} jobs{}",
+        action,
+        indices.len(),
+        change
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Status;
+
+    /// the indices of a selection out of 20 jobs, panics for anything else
+    fn indices(s: &str) -> Vec<usize> {
+        let rdr = Records::from(vec![Record::test("Acme", &["01-09-2026"]); 20]);
+        match parse(s).unwrap() {
+            selection @ Selection::Indices(_) => selection.indices(&rdr).unwrap(),
+            other => panic!("{} is {:?}", s, other),
+        }
+    }
+
+    #[test]
+    fn parse_indices_and_ranges() {
+        assert_eq!(indices("3"), [3]);
+        assert_eq!(indices("3-7,12"), [3, 4, 5, 6, 7, 12]);
+        assert_eq!(indices(" 5 - 6 , 1 "), [1, 5, 6]);
+        // sorted without duplicates
+        assert_eq!(indices("4,2-4,2"), [2, 3, 4]);
+        assert_eq!(indices("0-0"), [0]);
+    }
+
+    #[test]
+    fn parse_rejects_broken_ranges() {
+        for s in [
+            "7-3",
+            "3-",
+            "-3",
+            "1-2-3",
+            ",",
+            "",
+            "99999999999999999999999",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+        // huge ranges are fine until we look for a job that does not exist
+        let rdr = Records::from(vec![Record::test("Acme", &["01-09-2026"]); 2]);
+        for (s, missing) in [
+            ("0-99999999999", 2),
+            ("0-18446744073709551615", 2),
+            ("1,5-7", 5),
+            ("9", 9),
+        ] {
+            let err = parse(s).unwrap().indices(&rdr).unwrap_err();
+            assert_eq!(err.downcast::<NotFound>().unwrap().0, missing, "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_names_and_queries() {
+        assert!(matches!(parse("acme").unwrap(), Selection::Name(n) if n == "acme"));
+        assert!(
+            matches!(parse("\"Acme Backend\"").unwrap(), Selection::Name(n) if n == "Acme Backend")
+        );
+        assert!(matches!(parse("3a").unwrap(), Selection::Name(_)));
+        assert!(matches!(
+            parse("status:pending stale:yes").unwrap(),
+            Selection::Query(_)
+        ));
+        assert!(matches!(
+            parse("acme place:berlin").unwrap(),
+            Selection::Query(_)
+        ));
+    }
+
+    #[test]
+    fn names_only_match_the_candidates() {
+        let rdr = Records::from(vec![
+            Record::test("Acme", &["01-09-2026"]).with_status(Status::Rejected),
+            Record::test("Bar", &["01-09-2026"]).with_subname("Acme Cloud"),
+            Record::test("acme", &["05-09-2026"]).with_subname("Frontend"),
+        ]);
+        let acme = parse("acme").unwrap();
+        let pick = |candidate: fn(&Record) -> bool| single(Some(&acme), &rdr, candidate).unwrap();
+        assert_eq!(pick(|r| r.status == Status::Rejected), Some(0));
+        assert_eq!(pick(|r| r.name == "Bar"), Some(1));
+        assert_eq!(pick(|_| false), None);
+        assert_eq!(
+            super::indices(Some(&acme), &rdr, |r| r.subname == "Frontend").unwrap(),
+            [2]
+        );
+        // the company and the job together
+        let frontend = parse("\"acme frontend\"").unwrap();
+        assert_eq!(single(Some(&frontend), &rdr, |_| true).unwrap(), Some(2));
+    }
+}
@@ -0,0 +1,122 @@
+use std::cmp::Ordering;
+
+use serde::Deserialize;
+
+use crate::{records::Record, workflow};
+
+/// What we can sort the jobs by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SortKey {
+    /// the last action
+    Date,
+    /// the first action
+    FirstDate,
+    /// the company
+    Name,
+    /// the job name
+    Subname,
+    /// the status in the order of the workflow
+    Status,
+    /// the place
+    Place,
+    /// the stage
+    Stage,
+}
+
+impl SortKey {
+    /// compare two records by this key
+    fn compare(&self, a: &Record, b: &Record) -> Ordering {
+        let text = |s: &String| s.to_lowercase();
+        match self {
+            SortKey::Date => a.last_action().cmp(&b.last_action()),
+            SortKey::FirstDate => a.dates().first().cmp(&b.dates().first()),
+            SortKey::Name => text(&a.name).cmp(&text(&b.name)),
+            SortKey::Subname => text(&a.subname).cmp(&text(&b.subname)),
+            SortKey::Status => {
+                let statuses = workflow::statuses();
+                let position = |r: &Record| statuses.iter().position(|s| *s == r.status);
+                position(a).cmp(&position(b))
+            }
+            SortKey::Place => text(&a.place).cmp(&text(&b.place)),
+            SortKey::Stage => text(&a.stage).cmp(&text(&b.stage)),
+        }
+    }
+}
+
+/// sort the rows by the keys, the first key counts most, the indices stay with their record
+pub(crate) fn sort(rows: &mut [(usize, &Record)], keys: &[SortKey], reverse: bool) {
+    rows.sort_by(|(_, a), (_, b)| {
+        keys.iter()
+            .fold(Ordering::Equal, |o, key| o.then_with(|| key.compare(a, b)))
+    });
+    if reverse {
+        rows.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Status;
+
+    /// the indices in the sorted order
+    fn sorted(records: &[Record], keys: &[SortKey], reverse: bool) -> Vec<usize> {
+        let mut rows = records.iter().enumerate().collect::<Vec<_>>();
+        sort(&mut rows, keys, reverse);
+        rows.into_iter().map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn the_first_key_counts_most() {
+        let records = [
+            Record::test("bar", &["05-09-2026"]),
+            Record::test("Acme", &["10-09-2026"]).with_status(Status::Rejected),
+            Record::test("acme", &["01-09-2026"]),
+            Record::test("Acme", &["01-09-2026", "03-09-2026"]),
+        ];
+        // the name ignores case, the date decides between the acmes
+        assert_eq!(
+            sorted(&records, &[SortKey::Name, SortKey::Date], false),
+            [2, 3, 1, 0]
+        );
+        assert_eq!(
+            sorted(&records, &[SortKey::Name, SortKey::FirstDate], false),
+            [2, 3, 1, 0]
+        );
+        // pending comes before rejected in the workflow
+        assert_eq!(
+            sorted(&records, &[SortKey::Status, SortKey::Date], false),
+            [2, 3, 0, 1]
+        );
+    }
+
+    #[test]
+    fn equal_records_keep_their_order() {
+        let records = [
+            Record::test("Acme", &["01-09-2026"]).with_place("Berlin"),
+            Record::test("Bar", &["01-09-2026"]).with_place("Hamburg"),
+            Record::test("Baz", &["01-09-2026"]).with_place("berlin"),
+        ];
+        assert_eq!(sorted(&records, &[SortKey::Date], false), [0, 1, 2]);
+        assert_eq!(sorted(&records, &[SortKey::Place], false), [0, 2, 1]);
+        assert_eq!(sorted(&records, &[], false), [0, 1, 2]);
+    }
+
+    #[test]
+    fn reverse_turns_the_whole_order() {
+        let records = [
+            Record::test("Bar", &["01-09-2026"]).with_stage("Onsite"),
+            Record::test("Acme", &["01-09-2026"]).with_stage("Screen"),
+            Record::test("Acme", &["05-09-2026"]).with_stage("Onsite"),
+        ];
+        assert_eq!(
+            sorted(&records, &[SortKey::Stage, SortKey::Name], true),
+            [1, 0, 2]
+        );
+        assert_eq!(
+            sorted(&records, &[SortKey::Name, SortKey::Date], true),
+            [0, 2, 1]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    config,
+    exit::{self, Outcome},
+    records::{Records, StageResult},
+    selection::{self, Selection},
+};
+
+/// the position of the stage in the template, matched case insensitive
+pub(crate) fn position(name: &str) -> Option<usize> {
+    config::get()
+        .stages
+        .template
+        .iter()
+        .position(|t| t.eq_ignore_ascii_case(name.trim()))
+}
+
+/// start the stage `name` of the template for the selected jobs
+pub(crate) fn add(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    name: &str,
+    label: String,
+) -> anyhow::Result<Outcome> {
+    let template = &config::get().stages.template;
+    let Some(pos) = position(name) else {
+        return Err(exit::invalid(format!(
+            "{} is not a stage, the stages are: {}. Use --label for anything else",
+            name,
+            template.join(", ")
+        )));
+    };
+    let indices = selection::indices(jobs, rdr, |r| r.status.is_open())?;
+    if indices.is_empty()
+        || !selection::confirm(
+            rdr,
+            &indices,
+            "move",
+            &format!(" to the stage {}", template[pos]),
+        )?
+    {
+        return Ok(Outcome::Unchanged);
+    }
+    for i in indices {
+        rdr.get_mut(i)
+            .unwrap()
+            .add_stage(template[pos].clone(), label.clone());
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
+
+/// set how the current stage of the selected jobs went, we only ask if there is more than one
+pub(crate) fn set_result(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    result: StageResult,
+) -> anyhow::Result<Outcome> {
+    let indices = selection::indices(jobs, rdr, |r| !r.stages.is_empty())?;
+    if let Some(i) = indices
+        .iter()
+        .find(|i| rdr.get(**i).unwrap().stages.is_empty())
+    {
+        let record = rdr.get(*i).unwrap();
+        return Err(exit::invalid(format!(
+            "{} | {} has no stages yet, add one with `job-data stage add {} <stage>`",
+            record.name, record.subname, i
+        )));
+    }
+    if indices.is_empty()
+        || (indices.len() > 1
+            && !selection::confirm(
+                rdr,
+                &indices,
+                "set the result of",
+                &format!(" to {}", result),
+            )?)
+    {
+        return Ok(Outcome::Unchanged);
+    }
+    for i in indices {
+        rdr.get_mut(i).unwrap().set_stage_result(result);
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
+
+/// set the stage text of the selected jobs
+pub(crate) fn set(
+    rdr: &mut Records,
+    jobs: Option<&Selection>,
+    stage: String,
+) -> anyhow::Result<Outcome> {
+    let indices = selection::indices(jobs, rdr, |_| true)?;
+    if let [index] = indices[..] {
+        println!(
+            "Chainging from {} to {}",
+            rdr.get(index).unwrap().stage,
+            stage
+        );
+    }
+    if indices.is_empty()
+        || !selection::confirm(rdr, &indices, "change", &format!(" to the stage {}", stage))?
+    {
+        return Ok(Outcome::Unchanged);
+    }
+    for i in indices {
+        rdr.get_mut(i).unwrap().set_stage(stage.clone());
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
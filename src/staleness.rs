@@ -0,0 +1,284 @@
+use time::{Date, Duration, Weekday};
+use yansi::Paint;
+
+use crate::{
+    clock,
+    config::{self, GhostedRule, Staleness, StalenessRule},
+    exit::Outcome,
+    output::{self, Format},
+    prompt,
+    records::{Record, Records},
+    types::Status,
+    workflow,
+};
+
+/// days from `from` to `to`, only counting monday to friday if `working_days` is set
+fn elapsed_days(from: Date, to: Date, working_days: bool) -> i64 {
+    if !working_days {
+        return (to - from).whole_days();
+    }
+    let mut days = 0;
+    let mut d = from;
+    while d < to {
+        d += Duration::days(1);
+        if !matches!(d.weekday(), Weekday::Saturday | Weekday::Sunday) {
+            days += 1;
+        }
+    }
+    days
+}
+
+/// does the rule apply to the record
+fn matches(rule: &StalenessRule, r: &Record) -> bool {
+    let status = rule.status.as_ref().map(|s| *s == r.status).unwrap_or(true);
+    let stage = match rule.stage.as_deref() {
+        None => true,
+        Some("") => r.stage.trim().is_empty(),
+        Some(s) => r.stage.to_lowercase().contains(&s.to_lowercase()),
+    };
+    status && stage
+}
+
+/// is the record stale at `today` according to the configured rules
+pub(crate) fn is_stale(r: &Record, today: Date) -> bool {
+    stale_by(&config::get().staleness, r, today)
+}
+
+/// is the record stale at `today` according to the `staleness` rules
+fn stale_by(staleness: &Staleness, r: &Record, today: Date) -> bool {
+    // a record without a date is broken, the doctor repairs it
+    let Some(last) = r.dates().last() else {
+        return false;
+    };
+    let (days, working_days) = match staleness.rules.iter().find(|rule| matches(rule, r)) {
+        Some(rule) => (rule.days, rule.working_days),
+        None if r.status == Status::Todo => return false,
+        None => (staleness.days, staleness.working_days),
+    };
+    elapsed_days(last.date(), today, working_days) >= days as i64
+}
+
+/// should the record be moved to ghosted at `today`
+pub(crate) fn is_ghosted(r: &Record, today: Date) -> bool {
+    ghosted_by(&config::get().staleness.ghosted, r, today)
+}
+
+/// should the record be moved to ghosted at `today` according to the `ghosted` rule
+fn ghosted_by(ghosted: &GhostedRule, r: &Record, today: Date) -> bool {
+    ghosted.enabled
+        && matches!(r.status, Status::Applied | Status::Pending)
+        && workflow::allows(r.status, Status::Ghosted)
+        && r.dates().last().is_some_and(|last| {
+            elapsed_days(last.date(), today, ghosted.working_days) >= ghosted.days as i64
+        })
+}
+
+/// the indices of all records we propose to move to ghosted
+pub(crate) fn ghosted(rdr: &Records) -> Vec<usize> {
+    let today = clock::today();
+    rdr.iter()
+        .enumerate()
+        .filter(|(_, r)| is_ghosted(r, today))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// show which records we would move to ghosted in `format` and move them if `apply` is set
+/// and the user agrees
+pub(crate) fn run_ghosted(
+    rdr: &mut Records,
+    apply: bool,
+    format: Format,
+) -> anyhow::Result<Outcome> {
+    if format != Format::Table {
+        let rows = ghosted(rdr)
+            .into_iter()
+            .map(|i| (i, rdr.get(i).unwrap()))
+            .collect::<Vec<_>>();
+        output::write(format, &rows, &[])?;
+        return Ok(Outcome::Done);
+    }
+    // without `apply` we only show them, so nothing to change is fine
+    let nothing = if apply {
+        Outcome::Unchanged
+    } else {
+        Outcome::Done
+    };
+    if !config::get().staleness.ghosted.enabled {
+        println!("Ghosted detection is disabled, enable it with `[staleness.ghosted] enabled = true` in the config");
+        return Ok(nothing);
+    }
+    let indices = ghosted(rdr);
+    if indices.is_empty() {
+        println!("No pending application looks ghosted");
+        return Ok(nothing);
+    }
+    println!("These applications would change to Ghosted:");
+    for i in &indices {
+        let r = rdr.get(*i).unwrap();
+        println!(
+            "{:2} | {} | {} | last action {}",
+            i,
+            r.name.bold(),
+            r.subname.bold(),
+            r.date_string(false)
+        );
+    }
+    if !apply {
+        println!("Run `job-data ghosted --apply` to change them");
+        return Ok(Outcome::Done);
+    }
+    if !prompt::confirm(&format!("Move {} applications to Ghosted", indices.len()))? {
+        return Ok(Outcome::Unchanged);
+    }
+    for i in indices {
+        rdr.get_mut(i).unwrap().set_status(Status::Ghosted);
+    }
+    rdr.write()?;
+    Ok(Outcome::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn elapsed_days_counts_every_day() {
+        assert_eq!(
+            elapsed_days(date!(2026 - 10 - 09), date!(2026 - 10 - 16), false),
+            7
+        );
+        assert_eq!(
+            elapsed_days(date!(2026 - 10 - 16), date!(2026 - 10 - 16), false),
+            0
+        );
+    }
+
+    #[test]
+    fn elapsed_days_skips_weekends_for_working_days() {
+        // friday to friday
+        assert_eq!(
+            elapsed_days(date!(2026 - 10 - 09), date!(2026 - 10 - 16), true),
+            5
+        );
+        // friday to monday
+        assert_eq!(
+            elapsed_days(date!(2026 - 10 - 02), date!(2026 - 10 - 05), true),
+            1
+        );
+        // saturday to sunday
+        assert_eq!(
+            elapsed_days(date!(2026 - 10 - 10), date!(2026 - 10 - 11), true),
+            0
+        );
+    }
+
+    #[test]
+    fn stale_after_the_default_days() {
+        let staleness = Staleness {
+            days: 7,
+            ..Default::default()
+        };
+        let today = clock::today();
+        assert!(stale_by(
+            &staleness,
+            &Record::test("Acme", &["09-10-2026"]),
+            today
+        ));
+        assert!(!stale_by(
+            &staleness,
+            &Record::test("Acme", &["10-10-2026"]),
+            today
+        ));
+        // todo jobs are never stale without a rule
+        assert!(!stale_by(
+            &staleness,
+            &Record::test("Acme", &["01-09-2026"]).with_status(Status::Todo),
+            today
+        ));
+    }
+
+    #[test]
+    fn stale_with_working_days() {
+        let staleness = Staleness {
+            days: 5,
+            working_days: true,
+            ..Default::default()
+        };
+        let today = clock::today();
+        // five working days but seven calendar days
+        assert!(stale_by(
+            &staleness,
+            &Record::test("Acme", &["09-10-2026"]),
+            today
+        ));
+        assert!(!stale_by(
+            &staleness,
+            &Record::test("Acme", &["12-10-2026"]),
+            today
+        ));
+    }
+
+    #[test]
+    fn the_first_matching_rule_wins() {
+        let staleness = Staleness {
+            days: 30,
+            rules: vec![
+                StalenessRule {
+                    status: Some(Status::Pending),
+                    stage: Some("interview".to_string()),
+                    days: 3,
+                    working_days: true,
+                },
+                StalenessRule {
+                    status: Some(Status::Pending),
+                    stage: None,
+                    days: 10,
+                    working_days: false,
+                },
+            ],
+            ..Default::default()
+        };
+        let today = clock::today();
+        let interview = Record::test("Acme", &["12-10-2026"]).with_stage("Second Interview");
+        assert!(stale_by(&staleness, &interview, today));
+        assert!(!stale_by(
+            &staleness,
+            &Record::test("Acme", &["12-10-2026"]),
+            today
+        ));
+        assert!(stale_by(
+            &staleness,
+            &Record::test("Acme", &["06-10-2026"]),
+            today
+        ));
+        // no rule for interviewing, so the default of 30 days
+        assert!(!stale_by(
+            &staleness,
+            &Record::test("Acme", &["06-10-2026"]).with_status(Status::Interviewing),
+            today
+        ));
+    }
+
+    #[test]
+    fn records_without_a_date_are_neither_stale_nor_ghosted() {
+        let staleness = Staleness {
+            days: 0,
+            ..Default::default()
+        };
+        let ghosted = GhostedRule {
+            enabled: true,
+            days: 0,
+            working_days: false,
+        };
+        let today = clock::today();
+        let r = Record::test("Acme", &[]);
+        assert!(!stale_by(&staleness, &r, today));
+        assert!(!ghosted_by(&ghosted, &r, today));
+        let r = Record::test("Acme", &["16-10-2026"]);
+        assert!(stale_by(&staleness, &r, today));
+        assert!(ghosted_by(&ghosted, &r, today));
+    }
+}
@@ -1,54 +1,97 @@
 use ratatui::{
     crossterm::event::{self, KeyCode},
-    layout::{Constraint, Position, Rect},
-    widgets::{Block, Clear, Paragraph},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Clear, List, ListState, Paragraph},
     Frame,
 };
 
-use crate::types::{center, WindowFocus, GuiState};
+use crate::{
+    config,
+    types::{center, GuiState, WindowFocus},
+};
 
-/// draw the status edit frame
+/// draw the stage edit frame, without a template we only ask for the stage text
 pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
-    if let WindowFocus::StageEdit(ref txt, _) = state.focus {
+    if let WindowFocus::StageEdit(selected, ref txt, _) = state.focus {
+        let template = &config::get().stages.template;
+        if template.is_empty() {
+            let area = center(
+                frame.area(),
+                Constraint::Percentage(20),
+                Constraint::Length(3), // top and bottom border + content
+            );
+            let text_input = Paragraph::new(txt.to_owned()).block(Block::bordered().title("Stage"));
+            frame.render_widget(Clear, area);
+            frame.render_widget(text_input, area);
+            frame.set_cursor_position(Position::new(
+                area.x + txt.len() as u16 + 1,
+                // Move one line down, from the border to the input line
+                area.y + 1,
+            ));
+            return;
+        }
         let area = center(
             frame.area(),
             Constraint::Percentage(20),
-            Constraint::Length(3), // top and bottom border + content
+            Constraint::Length(template.len() as u16 + 5), // borders of both blocks + label line
         );
-        let text_input =
-            Paragraph::new(txt.to_owned()).block(Block::bordered().title("Stage Info"));
+        let l = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(area);
+        let list = List::new(template.iter().map(String::as_str))
+            .highlight_style(Style::new().reversed())
+            .highlight_symbol(">>")
+            .block(Block::bordered().title("Next stage"));
+        let text_input = Paragraph::new(txt.to_owned()).block(Block::bordered().title("Label"));
         frame.render_widget(Clear, area);
-        frame.render_widget(text_input, area);
+        frame.render_stateful_widget(
+            list,
+            l[0],
+            &mut ListState::default().with_selected(Some(selected)),
+        );
+        frame.render_widget(text_input, l[1]);
         frame.set_cursor_position(Position::new(
-            area.x + txt.len() as u16 + 1,
+            l[1].x + txt.len() as u16 + 1,
             // Move one line down, from the border to the input line
-            area.y + 1,
+            l[1].y + 1,
         ))
     }
 }
 
-/// handle inputs for status edit frame
+/// handle inputs for stage edit frame
 pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
-    let rdr = &mut state.rdr;
+    let WindowFocus::StageEdit(ref mut selected, ref mut txt, real_index) = state.focus else {
+        return;
+    };
+    let template = &config::get().stages.template;
     match key.code {
         KeyCode::Esc => {
             state.focus = WindowFocus::Table;
         }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(template.len().saturating_sub(1));
+        }
         KeyCode::Enter => {
-            if let WindowFocus::StageEdit(ref txt, real_index) = state.focus {
-                rdr.get_mut(real_index).unwrap().set_stage(txt.clone());
+            if template.is_empty() {
+                let stage = std::mem::take(txt);
+                state.rdr.get_mut(real_index).unwrap().set_stage(stage);
+            } else if let Some(name) = template.get(*selected) {
+                let label = std::mem::take(txt);
+                state
+                    .rdr
+                    .get_mut(real_index)
+                    .unwrap()
+                    .add_stage(name.clone(), label);
             }
             state.focus = WindowFocus::Table;
         }
         KeyCode::Char(char) => {
-            if let WindowFocus::StageEdit(ref mut txt, _) = state.focus {
-                txt.push(char);
-            }
+            txt.push(char);
         }
         KeyCode::Backspace => {
-            if let WindowFocus::StageEdit(ref mut txt, _) = state.focus {
-                txt.pop();
-            }
+            txt.pop();
         }
         _ => {}
     };
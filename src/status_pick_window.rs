@@ -0,0 +1,68 @@
+use ratatui::{
+    crossterm::event::{self, KeyCode},
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, List, ListState},
+    Frame,
+};
+
+use crate::types::{center, GuiState, Status, WindowFocus};
+
+/// draw the status picker
+pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
+    if let WindowFocus::StatusPick(ref next, selected, _) = state.focus {
+        let area = center(
+            frame.area(),
+            Constraint::Percentage(20),
+            Constraint::Length(next.len() as u16 + 2), // top and bottom border + content
+        );
+        let list = List::new(
+            next.iter()
+                .map(|s| Line::styled(s.name(), Style::default().fg(s.color()))),
+        )
+        .highlight_style(Style::new().reversed())
+        .highlight_symbol(">>")
+        .block(Block::bordered().title("Next Status"));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(
+            list,
+            area,
+            &mut ListState::default().with_selected(Some(selected)),
+        );
+    }
+}
+
+/// handle inputs for the status picker
+pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
+    let WindowFocus::StatusPick(ref next, ref mut selected, real_index) = state.focus else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            state.focus = WindowFocus::Table;
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(next.len() - 1);
+        }
+        KeyCode::Enter => {
+            let status = next[*selected];
+            let record = state.rdr.get_mut(real_index).unwrap();
+            if record.status == status {
+                state.focus = WindowFocus::Table;
+                return;
+            }
+            record.set_status(status);
+            state.changed_this_exection.insert(real_index);
+            state.focus = if status == Status::Rejected {
+                WindowFocus::Rejection(0, String::new(), real_index)
+            } else {
+                WindowFocus::TransitionComment(String::new(), real_index)
+            };
+        }
+        _ => {}
+    }
+}
@@ -5,7 +5,10 @@ use ratatui::{
     Frame,
 };
 
-use crate::types::{GuiState, Status, DATE_STRING, FORMAT};
+use crate::{
+    clock,
+    types::{GuiState, Status, FORMAT},
+};
 
 /// Span for a single value in status
 fn single_val<'a>(st: &str, val: usize, total: usize, color: Color) -> Span<'a> {
@@ -19,24 +22,19 @@ fn single_val<'a>(st: &str, val: usize, total: usize, color: Color) -> Span<'a>
 /// Returns a line that gives all the stats
 fn stats<'a>(state: &'a GuiState) -> Line<'a> {
     let total = state.rdr.len();
-    let todos = state
-        .rdr
-        .iter()
-        .filter(|r| r.status == Status::Todo)
-        .count();
-    let pending_iter = state.rdr.iter().filter(|r| r.status == Status::Pending);
-    let pending = pending_iter.clone().count();
-    let pending_past = pending_iter.filter(|r| r.is_old()).count();
-    let rejected = state
-        .rdr
-        .iter()
-        .filter(|r| r.status == Status::Rejected)
-        .count();
-    let declined = state
-        .rdr
-        .iter()
-        .filter(|r| r.status == Status::Declined)
-        .count();
+    let mut spans = Vec::new();
+    for status in Status::ALL {
+        let iter = state.rdr.iter().filter(|r| r.status == status);
+        let count = iter.clone().count();
+        if status == Status::Pending {
+            let past = iter.filter(|r| r.is_old()).count();
+            spans.push(single_val("Pnd", count - past, total, status.color()));
+            spans.push(single_val("Pnd+", count, total, status.color()));
+        } else if count > 0 || status == Status::Todo {
+            // we only show the ones we use to keep the bar short
+            spans.push(single_val(status.short(), count, total, status.color()));
+        }
+    }
     let last = state
         .rdr
         .iter()
@@ -44,16 +42,11 @@ fn stats<'a>(state: &'a GuiState) -> Line<'a> {
         .max()
         .unwrap().format(&FORMAT).unwrap();
 
-    let spans = vec![
-        single_val("Todo", todos, total, Color::Red),
-        single_val("Pnd", pending - pending_past, total, Color::Yellow),
-        single_val("Pnd+", pending, total, Color::Yellow),
-        single_val("Rej", rejected, total, Color::Green),
-        single_val("Decl", declined, total, Color::Green),
+    spans.extend([
         Span::styled(format!("#: {}", total), Style::default()),
         Span::styled(format!(" | Edit: {}", last), Style::default()),
-        Span::styled(format!(" | Today: {}", *DATE_STRING), Style::default()),
-    ];
+        Span::styled(format!(" | Today: {}", clock::today_string()), Style::default()),
+    ]);
     Line::from(spans)
 }
 
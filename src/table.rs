@@ -0,0 +1,335 @@
+use std::{io::IsTerminal, ops::Range};
+
+use ratatui::crossterm::terminal;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use yansi::{Paint, Style};
+
+use crate::{query::Query, records::Record};
+
+/// A column of the table
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum Column {
+    /// the index to use in other commands
+    Index,
+    Status,
+    /// the date of the last action
+    Date,
+    /// the company
+    Name,
+    /// the job
+    Subname,
+    Stage,
+    /// the additional information
+    Info,
+    Place,
+}
+
+/// The columns of the list
+pub(crate) const LIST: &[Column] = &[
+    Column::Index,
+    Column::Status,
+    Column::Date,
+    Column::Name,
+    Column::Subname,
+    Column::Stage,
+    Column::Place,
+];
+
+/// The columns when we show everything, i.e., for a search
+pub(crate) const ALL: &[Column] = &[
+    Column::Index,
+    Column::Status,
+    Column::Date,
+    Column::Name,
+    Column::Subname,
+    Column::Stage,
+    Column::Info,
+    Column::Place,
+];
+
+/// We do not shrink a column below this width to fit the terminal
+const MIN_WIDTH: usize = 8;
+
+/// Between two columns
+const SEPARATOR: &str = " | ";
+
+/// Where the text goes if the column is wider
+#[derive(Clone, Copy)]
+enum Align {
+    Center,
+    Right,
+}
+
+impl Column {
+    /// the title in the header
+    fn header(self) -> &'static str {
+        match self {
+            Column::Index => "",
+            Column::Status => "Status",
+            Column::Date => "Last Date",
+            Column::Name => "Name",
+            Column::Subname => "Subname",
+            Column::Stage => "Stage",
+            Column::Info => "Info",
+            Column::Place => "Place",
+        }
+    }
+
+    /// the field in the data file, for the other formats
+    pub(crate) fn field(self) -> &'static str {
+        match self {
+            Column::Index => "Index",
+            Column::Status => "Status",
+            Column::Date => "LastActionDate",
+            Column::Name => "Name",
+            Column::Subname => "Subname",
+            Column::Stage => "Stage",
+            Column::Info => "AdditionalInfo",
+            Column::Place => "Place",
+        }
+    }
+
+    /// can we shrink the column to fit the terminal, the others are short anyway
+    fn shrinks(self) -> bool {
+        !matches!(self, Column::Index | Column::Status | Column::Date)
+    }
+
+    /// how the values pad
+    fn pad(self) -> (Align, char) {
+        match self {
+            Column::Index => (Align::Right, ' '),
+            Column::Status | Column::Date | Column::Name => (Align::Center, '-'),
+            _ => (Align::Center, ' '),
+        }
+    }
+
+    /// the value of the record, stale ones are dimmed
+    fn cell(self, index: usize, r: &Record, show_time: bool, query: &Query) -> Cell {
+        let dim = |style: Style| if r.is_old() { style.dim() } else { style };
+        let hl = |text: &str, style| Cell::new(text.to_string(), style, query.ranges(text));
+        match self {
+            Column::Index => Cell::new(index.to_string(), dim(Style::new()), Vec::new()),
+            Column::Status => {
+                let status = r.status.print();
+                Cell::new(status.value, dim(status.style), Vec::new())
+            }
+            Column::Date => Cell::new(r.date_string(show_time), dim(Style::new()), Vec::new()),
+            Column::Name => hl(&r.name, dim(Style::new().bold())),
+            Column::Subname => hl(&r.subname, dim(Style::new().bold())),
+            Column::Stage => hl(&r.stage, dim(Style::new())),
+            Column::Info => hl(&r.additional_info, dim(Style::new())),
+            Column::Place => hl(&r.place, Style::new()),
+        }
+    }
+}
+
+/// A value in the table with the matches of a search
+struct Cell {
+    text: String,
+    style: Style,
+    /// the byte ranges we show in yellow
+    ranges: Vec<Range<usize>>,
+}
+
+impl Cell {
+    fn new(text: String, style: Style, ranges: Vec<Range<usize>>) -> Self {
+        Cell {
+            text,
+            style,
+            ranges,
+        }
+    }
+
+    /// how many columns of the terminal the text takes
+    fn width(&self) -> usize {
+        self.text.width()
+    }
+
+    /// the text fit into `width`, cut with an ellipsis if it is wider.
+    /// The last column is not padded
+    fn render(&self, width: usize, (align, fill): (Align, char), last: bool) -> String {
+        let (end, ellipsis) = cut(&self.text, width);
+        let shown = self.text[..end].width() + usize::from(ellipsis);
+        let pad = if last { 0 } else { width.saturating_sub(shown) };
+        let (left, right) = match align {
+            Align::Center => (pad / 2, pad - pad / 2),
+            Align::Right => (pad, 0),
+        };
+        let fill = |n: usize| fill.to_string().repeat(n);
+        let matched = self.style.yellow().underline();
+        let mut parts = vec![(fill(left), self.style)];
+        let mut done = 0;
+        for r in self.ranges.iter().filter(|r| r.start < end) {
+            let r = r.start..r.end.min(end);
+            parts.push((self.text[done..r.start].to_string(), self.style));
+            parts.push((self.text[r.clone()].to_string(), matched));
+            done = r.end;
+        }
+        parts.push((self.text[done..end].to_string(), self.style));
+        if ellipsis {
+            parts.push(("…".to_string(), self.style));
+        }
+        parts.push((fill(right), self.style));
+        // paint neighbours with the same style at once
+        let mut merged: Vec<(String, Style)> = Vec::new();
+        for (part, style) in parts.into_iter().filter(|(p, _)| !p.is_empty()) {
+            match merged.last_mut() {
+                Some((last, s)) if *s == style => last.push_str(&part),
+                _ => merged.push((part, style)),
+            }
+        }
+        merged
+            .iter()
+            .map(|(p, style)| p.paint(*style).to_string())
+            .collect()
+    }
+}
+
+/// where to cut `text` so it fits into `width` and if we need an ellipsis
+fn cut(text: &str, width: usize) -> (usize, bool) {
+    if text.width() <= width {
+        return (text.len(), false);
+    }
+    // leave room for the ellipsis
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used + 1 > width {
+            return (text[..i].trim_end().len(), width > 0);
+        }
+    }
+    (text.len(), false)
+}
+
+/// the width of the terminal, `None` if we do not print to one
+fn terminal_width() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal::size().ok().map(|(w, _)| usize::from(w))
+}
+
+/// shrink the widest columns that can shrink until the table fits into `available`
+fn fit(widths: &mut [usize], columns: &[Column], available: usize) {
+    let mut total = widths.iter().sum::<usize>() + SEPARATOR.len() * widths.len().saturating_sub(1);
+    while total > available {
+        let Some(widest) = (0..widths.len())
+            .filter(|i| columns[*i].shrinks() && widths[*i] > MIN_WIDTH)
+            .max_by_key(|i| widths[*i])
+        else {
+            // it does not fit, the terminal wraps the rest
+            return;
+        };
+        widths[widest] -= 1;
+        total -= 1;
+    }
+}
+
+/// print the `columns` of the `rows` with their index, highlighting what the `query` matches
+pub(crate) fn print(rows: &[(usize, &Record)], columns: &[Column], show_time: bool, query: &Query) {
+    let cells = rows
+        .iter()
+        .map(|(i, r)| {
+            columns
+                .iter()
+                .map(|c| c.cell(*i, r, show_time, query))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut widths = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            cells
+                .iter()
+                .map(|row| row[i].width())
+                .fold(c.header().width(), usize::max)
+        })
+        .collect::<Vec<_>>();
+    if let Some(available) = terminal_width() {
+        fit(&mut widths, columns, available);
+    }
+    let line = |row: Vec<String>| row.join(SEPARATOR);
+    let header = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let header = Cell::new(c.header().to_string(), Style::new().underline(), Vec::new());
+            header.render(widths[i], (Align::Center, ' '), i + 1 == columns.len())
+        })
+        .collect();
+    println!("{}", line(header));
+    for row in cells {
+        let row = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| cell.render(widths[i], columns[i].pad(), i + 1 == columns.len()))
+            .collect();
+        println!("{}", line(row));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the text cut to `width` as shown
+    fn shown(text: &str, width: usize) -> String {
+        let (end, ellipsis) = cut(text, width);
+        format!("{}{}", &text[..end], if ellipsis { "…" } else { "" })
+    }
+
+    #[test]
+    fn cut_keeps_text_that_fits() {
+        assert_eq!(shown("Acme", 4), "Acme");
+        assert_eq!(shown("Acme", 10), "Acme");
+        assert_eq!(shown("", 0), "");
+    }
+
+    #[test]
+    fn cut_adds_an_ellipsis() {
+        assert_eq!(shown("Backend Developer", 8), "Backend…");
+        // no space before the ellipsis
+        assert_eq!(shown("Backend Developer", 9), "Backend…");
+        assert_eq!(shown("Acme", 1), "…");
+        assert_eq!(shown("Acme", 0), "");
+    }
+
+    #[test]
+    fn cut_measures_the_display_width() {
+        // umlauts take one column, but two bytes
+        assert_eq!(shown("Müller", 6), "Müller");
+        assert_eq!(shown("Müller GmbH", 4), "Mül…");
+        // wide characters take two columns
+        assert_eq!(shown("日本語", 6), "日本語");
+        assert_eq!(shown("日本語", 5), "日本…");
+        assert_eq!(shown("日本語", 4), "日…");
+        for width in 0..8 {
+            assert!(shown("日本語のエンジニア", width).width() <= width);
+        }
+    }
+
+    #[test]
+    fn fit_shrinks_the_widest_text_column() {
+        let columns = [Column::Index, Column::Status, Column::Name, Column::Place];
+        let mut widths = [2, 10, 30, 12];
+        // 63 wide with the separators
+        fit(&mut widths, &columns, 53);
+        assert_eq!(widths, [2, 10, 20, 12]);
+        // both are the widest in turn
+        fit(&mut widths, &columns, 42);
+        assert_eq!(widths, [2, 10, 11, 10]);
+        fit(&mut widths, &columns, 30);
+        assert_eq!(widths, [2, 10, 8, 8]);
+    }
+
+    #[test]
+    fn render_pads_to_the_width() {
+        yansi::disable();
+        let cell = Cell::new("Acme".to_string(), Style::new(), Vec::new());
+        assert_eq!(cell.render(8, (Align::Center, '-'), false), "--Acme--");
+        assert_eq!(cell.render(6, (Align::Right, ' '), false), "  Acme");
+        assert_eq!(cell.render(8, (Align::Center, ' '), true), "Acme");
+        assert_eq!(cell.render(3, (Align::Center, ' '), false), "Ac…");
+    }
+}
@@ -6,32 +6,45 @@ use ratatui::{
 use std::ops::ControlFlow;
 
 use crate::{
-    records::Record,
+    config,
+    query::Query,
+    records::{Record, StageResult},
+    stages,
     types::{AddFocusField, AddStruct, GuiState, Save, Status, WindowFocus},
+    workflow,
 };
 
+/// the text with the matches of the search highlighted
+fn highlight<'a>(text: &'a str, query: &Query) -> Line<'a> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for range in query.ranges(text) {
+        spans.push(Span::raw(&text[last..range.start]));
+        spans.push(Span::styled(
+            &text[range.clone()],
+            Style::new().yellow().underlined(),
+        ));
+        last = range.end;
+    }
+    spans.push(Span::raw(&text[last..]));
+    Line::from(spans)
+}
+
 /// draw a single record
-fn draw_record(index: usize, r: &Record) -> Row<'_> {
-    let color = match r.status {
-        Status::Todo => Color::Red,
-        Status::Pending => {
-            if r.is_old() {
-                Color::DarkGray
-            } else {
-                Color::Yellow
-            }
-        }
-        Status::Rejected => Color::Green,
-        Status::Declined => Color::Green,
+fn draw_record<'a>(index: usize, r: &'a Record, show_times: bool, query: &Query) -> Row<'a> {
+    let color = if r.status.is_open() && r.status != Status::Todo && r.is_old() {
+        Color::DarkGray
+    } else {
+        r.status.color()
     };
     Row::new(vec![
-        (index +1).to_string(),
-        r.status.to_string(),
-        r.date_string(),
-        r.name.to_owned(),
-        r.subname.to_owned(),
-        r.stage.to_owned(),
-        r.place.to_owned(),
+        Line::from((index +1).to_string()),
+        Line::from(r.status.name()),
+        Line::from(r.date_string(show_times)),
+        highlight(&r.name, query),
+        highlight(&r.subname, query),
+        highlight(&r.stage, query),
+        highlight(&r.place, query),
     ])
     .style(Style::default().fg(color))
 }
@@ -43,7 +56,7 @@ pub(crate) fn draw(frame: &mut Frame, r: Rect, state: &mut GuiState) {
         .iter()
         .enumerate()
         .filter(|(index, r)| state.filter(index, r))
-        .map(|(index, r)| draw_record(index, r));
+        .map(|(index, r)| draw_record(index, r, state.show_times, &state.query));
 
     // Columns widths are constrained in the same way as Layout...
     let widths = [
@@ -93,9 +106,11 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) -> Contro
         }
         KeyCode::Enter => {
             let real_index = state.get_real_index();
-            if let Some(record) = state.rdr.get_mut(real_index) {
-                record.next_stage();
-                state.changed_this_exection.insert(real_index);
+            if let Some(record) = state.rdr.get(real_index) {
+                let next = workflow::next(record.status);
+                if !next.is_empty() {
+                    state.focus = WindowFocus::StatusPick(next, 0, real_index);
+                }
             }
         }
         KeyCode::Delete => {
@@ -105,12 +120,38 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) -> Contro
         KeyCode::Char('v') => {
             state.view = state.view.next();
         }
+        KeyCode::Char('t') => {
+            state.show_times = !state.show_times;
+        }
         KeyCode::Char('s') => {
             let real_index = state.get_real_index();
             // yes, the state is on the table index not the real index
             state.changed_this_exection.insert(real_index);
-            let txt = state.rdr.get(real_index).unwrap().stage.clone();
-            state.focus = WindowFocus::StageEdit(txt, real_index);
+            // we usually move on to the stage after the current one
+            let next = state
+                .rdr
+                .get(real_index)
+                .unwrap()
+                .stages
+                .last()
+                .and_then(|s| stages::position(&s.name))
+                .map_or(0, |p| {
+                    (p + 1).min(config::get().stages.template.len().saturating_sub(1))
+                });
+            state.focus = WindowFocus::StageEdit(next, String::new(), real_index);
+        }
+        KeyCode::Char('p') | KeyCode::Char('f') => {
+            let real_index = state.get_real_index();
+            let result = if key.code == KeyCode::Char('p') {
+                StageResult::Passed
+            } else {
+                StageResult::Failed
+            };
+            if let Some(record) = state.rdr.get_mut(real_index) {
+                if record.set_stage_result(result) {
+                    state.changed_this_exection.insert(real_index);
+                }
+            }
         }
         KeyCode::Char('?') => {
             state.focus = WindowFocus::Help;
@@ -131,6 +172,9 @@ pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) -> Contro
         KeyCode::Char('i') => {
             state.focus = WindowFocus::Info;
         }
+        KeyCode::Char('o') => {
+            state.focus = WindowFocus::Offers;
+        }
         KeyCode::Char('e') => {
             state.focus = WindowFocus::Add;
             let index = state.get_real_index();
@@ -0,0 +1,58 @@
+use ratatui::{
+    crossterm::event::{self, KeyCode},
+    layout::{Constraint, Position, Rect},
+    widgets::{Block, Clear, Paragraph},
+    Frame,
+};
+
+use crate::types::{center, GuiState, WindowFocus};
+
+/// draw the popup for the comment on a status change
+pub(crate) fn draw(frame: &mut Frame, _: Rect, state: &GuiState) {
+    if let WindowFocus::TransitionComment(ref txt, _) = state.focus {
+        let area = center(
+            frame.area(),
+            Constraint::Percentage(30),
+            Constraint::Length(3), // top and bottom border + content
+        );
+        let text_input = Paragraph::new(txt.to_owned())
+            .block(Block::bordered().title("Why did the status change (Esc to skip)"));
+        frame.render_widget(Clear, area);
+        frame.render_widget(text_input, area);
+        frame.set_cursor_position(Position::new(
+            area.x + txt.len() as u16 + 1,
+            // Move one line down, from the border to the input line
+            area.y + 1,
+        ))
+    }
+}
+
+/// handle inputs for the comment on a status change
+pub(crate) fn handle_input(key: event::KeyEvent, state: &mut GuiState) {
+    let WindowFocus::TransitionComment(ref mut txt, real_index) = state.focus else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            state.focus = WindowFocus::Table;
+        }
+        KeyCode::Enter => {
+            if !txt.is_empty() {
+                let comment = std::mem::take(txt);
+                state
+                    .rdr
+                    .get_mut(real_index)
+                    .unwrap()
+                    .comment_transition(comment);
+            }
+            state.focus = WindowFocus::Table;
+        }
+        KeyCode::Char(char) => {
+            txt.push(char);
+        }
+        KeyCode::Backspace => {
+            txt.pop();
+        }
+        _ => {}
+    };
+}
@@ -1,50 +1,59 @@
-use std::{collections::HashSet, sync::LazyLock};
+use std::{
+    collections::HashSet,
+    sync::LazyLock,
+    time::{Instant, SystemTime},
+};
 
-use anyhow::Context;
 use ratatui::{
     layout::{Constraint, Flex, Layout, Rect},
+    style::Color,
     widgets::TableState,
 };
 use serde::{Deserialize, Serialize};
 use time::{
     format_description::{self, BorrowedFormatItem},
-    OffsetDateTime, UtcOffset,
 };
-use yansi::{Paint, Painted};
+use yansi::Painted;
 
-use crate::records::{Record, Records};
+use crate::{
+    query::{Query, SearchMode},
+    records::{Record, Records},
+    workflow,
+};
 
 /// Time format
 pub(crate) static FORMAT: LazyLock<Vec<BorrowedFormatItem<'_>>> =
     LazyLock::new(|| format_description::parse("[day]-[month]-[year]").expect("error"));
 
-/// The local time
-pub(crate) static NOW: LazyLock<OffsetDateTime> = LazyLock::new(|| {
-    OffsetDateTime::now_local()
-        .context("Cannot get now")
-        .expect("Error")
-});
-/// The current offset
-pub(crate) static CURRENT_OFFSET: LazyLock<UtcOffset> = LazyLock::new(|| {
-    UtcOffset::current_local_offset()
-        .context("Could not get offset")
-        .expect("Error")
+/// Time format when we show the time of an action
+pub(crate) static DATE_TIME_FORMAT: LazyLock<Vec<BorrowedFormatItem<'_>>> = LazyLock::new(|| {
+    format_description::parse("[day]-[month]-[year] [hour]:[minute]").expect("error")
 });
-/// the date string of now
-pub(crate) static DATE_STRING: LazyLock<String> =
-    LazyLock::new(|| NOW.date().format(&FORMAT).expect("Error"));
+
 
 /// Status of a job application
-#[derive(Clone, Debug, Deserialize, Hash, Serialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Deserialize, Hash, Serialize, PartialEq, Eq, clap::ValueEnum)]
 pub(crate) enum Status {
     /// we need to do something
     Todo,
+    /// we sent the application
+    Applied,
     /// we are waiting for an update
     Pending,
+    /// we are in the interview process
+    Interviewing,
+    /// we got an offer
+    Offer,
+    /// we accepted the offer
+    Accepted,
     /// we got rejected
     Rejected,
     /// we declined the offer
     Declined,
+    /// we withdrew the application
+    Withdrawn,
+    /// we never heard back
+    Ghosted,
 }
 
 /// simple display function for status on cmd
@@ -52,34 +61,73 @@ impl std::fmt::Display for Status {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Status::Todo => f.write_str("Todo"),
+            Status::Applied => f.write_str("Applied"),
             Status::Pending => f.write_str("Pending"),
+            Status::Interviewing => f.write_str("Interviewing"),
+            Status::Offer => f.write_str("Offer"),
+            Status::Accepted => f.write_str("Accepted"),
             Status::Rejected => f.write_str("Rejected"),
             Status::Declined => f.write_str("Declined"),
+            Status::Withdrawn => f.write_str("Withdrawn"),
+            Status::Ghosted => f.write_str("Ghosted"),
         }
     }
 }
 
 impl Status {
+    /// all states in the order of a job hunt
+    pub(crate) const ALL: [Status; 10] = [
+        Status::Todo,
+        Status::Applied,
+        Status::Pending,
+        Status::Interviewing,
+        Status::Offer,
+        Status::Accepted,
+        Status::Rejected,
+        Status::Declined,
+        Status::Withdrawn,
+        Status::Ghosted,
+    ];
+
+    /// the name we show, can be changed in the config
+    pub(crate) fn name(&self) -> String {
+        workflow::name(*self).unwrap_or_else(|| self.to_string())
+    }
+
     /// display string for status
-    pub(crate) fn print(&self) -> Painted<&str> {
-        match self {
-            Status::Todo => "TODO".red(),
-            Status::Pending => "Pending".yellow(),
-            Status::Declined => "Declined".green(),
-            Status::Rejected => "Rejected".green(),
-        }
+    pub(crate) fn print(&self) -> Painted<String> {
+        let name = workflow::name(*self).unwrap_or_else(|| match self {
+            Status::Todo => "TODO".to_string(),
+            _ => self.to_string(),
+        });
+        Painted::new(name).fg(workflow::color(*self).cli())
+    }
+
+    /// the color of the status in the tui
+    pub(crate) fn color(&self) -> Color {
+        workflow::color(*self).tui()
     }
 
-    /// function to toggle status in order, returning the new status
-    #[must_use]
-    pub(crate) fn next(&self) -> Status {
+    /// short name for the summary
+    pub(crate) fn short(&self) -> &'static str {
         match self {
-            Status::Todo => Status::Pending,
-            Status::Pending => Status::Rejected,
-            Status::Rejected => Status::Todo,
-            Status::Declined => Status::Todo,
+            Status::Todo => "Todo",
+            Status::Applied => "Appl",
+            Status::Pending => "Pnd",
+            Status::Interviewing => "Intv",
+            Status::Offer => "Offr",
+            Status::Accepted => "Acc",
+            Status::Rejected => "Rej",
+            Status::Declined => "Decl",
+            Status::Withdrawn => "Wdrn",
+            Status::Ghosted => "Ghst",
         }
     }
+
+    /// are we still in the process for this job
+    pub(crate) fn is_open(&self) -> bool {
+        workflow::is_open(*self)
+    }
 }
 
 /// Find the center rectangle
@@ -94,9 +142,9 @@ pub(crate) fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -
 /// Which part of jobs we show
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum GuiView {
-    /// Show only non-old Pending and Todo jobs
+    /// Show only non-old open jobs and Todo jobs
     Normal,
-    /// Show only Pending jobs
+    /// Show all open jobs
     Old,
     /// Show all jobs
     All,
@@ -127,8 +175,22 @@ pub(crate) struct GuiState<'a> {
     pub(crate) changed_this_exection: HashSet<usize>,
     /// Are we searching something
     pub(crate) search: Option<String>,
+    /// How we match the words of the search
+    pub(crate) search_mode: SearchMode,
+    /// The parsed search
+    pub(crate) query: Query,
+    /// Why we could not parse the search
+    pub(crate) query_error: Option<String>,
     /// A job we want to add
     pub(crate) add: Option<AddStruct>,
+    /// Show the time of the last action and not only the date
+    pub(crate) show_times: bool,
+    /// The records as they are on disk, used to find local changes and as the base for merging
+    pub(crate) disk_records: Vec<Record>,
+    /// The modification time of the data file when we last read or wrote it
+    pub(crate) disk_modified: Option<SystemTime>,
+    /// When we last checked the data file for changes
+    pub(crate) last_disk_check: Instant,
 }
 
 impl<'a> GuiState<'a> {
@@ -139,16 +201,67 @@ impl<'a> GuiState<'a> {
             r.status == Status::Todo
                 || self.changed_this_exection.contains(index)
                 || match self.view {
-                    GuiView::Normal => r.status == Status::Pending && !r.is_old(),
-                    GuiView::Old => r.status == Status::Todo || r.status == Status::Pending,
+                    GuiView::Normal => r.status.is_open() && !r.is_old(),
+                    GuiView::Old => r.status.is_open(),
                     GuiView::All => true,
                 }
         } else {
-            let search_string = &self.search.as_ref().unwrap();
-            r.name.contains(*search_string) || r.subname.contains(*search_string)
+            self.query.matches(r)
+        }
+    }
+
+    /// parse the search text, while the text is broken we keep the last query that worked
+    pub(crate) fn update_query(&mut self) {
+        match Query::parse(self.search.as_deref().unwrap_or_default(), self.search_mode) {
+            Ok(query) => {
+                self.query = query;
+                self.query_error = None;
+            }
+            Err(e) => self.query_error = Some(e),
         }
     }
 
+    /// do we have changes that are not written to disk
+    pub(crate) fn has_local_changes(&self) -> bool {
+        // the order can differ as sorting is not stable for records with the same date
+        self.rdr.len() != self.disk_records.len()
+            || self.rdr.iter().any(|r| !self.disk_records.contains(r))
+    }
+
+    /// write the records and remember them as the state on disk
+    pub(crate) fn write(&mut self) -> anyhow::Result<()> {
+        self.rdr.write()?;
+        self.disk_records = self.rdr.0.clone();
+        self.disk_modified = Records::modified();
+        Ok(())
+    }
+
+    /// replace the records with `records`, keeping the selection on the same job if it still exists
+    pub(crate) fn replace_records(&mut self, records: Records) {
+        let selected = self
+            .table_state
+            .selected()
+            .and_then(|s| {
+                self.rdr
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, r)| self.filter(index, r))
+                    .nth(s)
+            })
+            .map(|(_, r)| (r.name.clone(), r.subname.clone()));
+        *self.rdr = records;
+        self.rdr.0.sort_unstable();
+        self.changed_this_exection = HashSet::new();
+        let position = selected.and_then(|(name, subname)| {
+            self.rdr
+                .iter()
+                .enumerate()
+                .filter(|(index, r)| self.filter(index, r))
+                .position(|(_, r)| r.name == name && r.subname == subname)
+        });
+        self.table_state.select(Some(position.unwrap_or(0)));
+    }
+
     /// get the index in the record vector from the selection of the table
     pub(crate) fn get_real_index(&self) -> usize {
         let index = self.table_state.selected().unwrap();
@@ -200,8 +313,16 @@ pub(crate) struct AddStruct {
 pub(crate) enum WindowFocus {
     /// The table
     Table,
-    /// The edit stage popup
-    StageEdit(String, usize),
+    /// The popup for the next stage with the selected stage of the template, the label and the record
+    StageEdit(usize, String, usize),
+    /// The popup to pick the next status with the possible statuses, the selected one and the record
+    StatusPick(Vec<Status>, usize, usize),
+    /// The offers side by side
+    Offers,
+    /// The popup for the comment on the last status change with the comment and the record
+    TransitionComment(String, usize),
+    /// The popup for the rejection reason with the selected reason, the comment and the record
+    Rejection(usize, String, usize),
     /// The help window
     Help,
     /// The search lower bar
@@ -210,6 +331,8 @@ pub(crate) enum WindowFocus {
     Add,
     /// the info popup
     Info,
+    /// the data file changed on disk while we have local changes
+    Reload,
 }
 
 /// Should we save the records to disk or not
@@ -0,0 +1,154 @@
+use inquire::{
+    autocompletion::{Autocomplete, Replacement},
+    validator::Validation,
+    CustomUserError, InquireError, Select, Text,
+};
+use time::Date;
+use yansi::Paint;
+
+use crate::{
+    clock, exit, prompt,
+    records::{Record, Records},
+    types::{Status, FORMAT},
+};
+
+/// Everything the wizard asks for
+#[derive(Clone, Debug)]
+pub(crate) struct Answers {
+    pub(crate) name: String,
+    pub(crate) subname: String,
+    pub(crate) place: String,
+    pub(crate) info: String,
+    pub(crate) stage: String,
+    pub(crate) status: Status,
+    /// when it happened, we only ask for new jobs
+    pub(crate) date: Option<Date>,
+}
+
+/// all distinct values of a field, to suggest them
+fn known(rdr: &Records, field: fn(&Record) -> &str) -> Vec<String> {
+    let mut values = rdr
+        .iter()
+        .map(field)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+    values.sort_by_key(|v| v.to_lowercase());
+    values.dedup();
+    values
+}
+
+/// Suggests the known values that contain the input, ignoring case
+#[derive(Clone)]
+struct Suggestions(Vec<String>);
+
+impl Autocomplete for Suggestions {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        let input = input.to_lowercase();
+        Ok(self
+            .0
+            .iter()
+            .filter(|v| v.to_lowercase().contains(&input))
+            .cloned()
+            .collect())
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        // without a highlighted one Tab takes the first suggestion
+        match highlighted {
+            Some(h) => Ok(Some(h)),
+            None => Ok(self.get_suggestions(input)?.into_iter().next()),
+        }
+    }
+}
+
+/// ask for a text starting with `current`, suggesting the `known` values
+fn text(label: &str, current: &str, known: Vec<String>) -> Result<String, InquireError> {
+    Text::new(label)
+        .with_initial_value(current)
+        .with_autocomplete(Suggestions(known))
+        .prompt()
+}
+
+/// ask for every field one after the other, starting with `current`.
+/// The status is one of `statuses`, `None` if the user gives up with Esc
+pub(crate) fn ask(
+    rdr: &Records,
+    current: Answers,
+    statuses: &[Status],
+) -> anyhow::Result<Option<Answers>> {
+    if !prompt::terminal() {
+        return Err(exit::invalid("The wizard needs a terminal"));
+    }
+    let answers = (|| {
+        let name = text("Company", &current.name, known(rdr, |r| &r.name))?;
+        let subname = text("Job", &current.subname, known(rdr, |r| &r.subname))?;
+        let place = text("Place", &current.place, known(rdr, |r| &r.place))?;
+        let info = text("Additional info", &current.info, Vec::new())?;
+        let stage = text("Stage", &current.stage, known(rdr, |r| &r.stage))?;
+        let names = statuses.iter().map(|s| s.name()).collect::<Vec<_>>();
+        let start = statuses
+            .iter()
+            .position(|s| *s == current.status)
+            .unwrap_or(0);
+        let status = Select::new("Status", names)
+            .with_starting_cursor(start)
+            .raw_prompt()?;
+        let date = match current.date {
+            Some(date) => Some(
+                Text::new("Date (dd-mm-yyyy or yyyy-mm-dd)")
+                    .with_initial_value(&date.format(&FORMAT).unwrap())
+                    .with_validator(|s: &str| {
+                        Ok(match crate::parse_date(s) {
+                            Ok(d) if d > clock::today() => {
+                                Validation::Invalid("The date cannot be in the future".into())
+                            }
+                            Ok(_) => Validation::Valid,
+                            Err(e) => Validation::Invalid(e.into()),
+                        })
+                    })
+                    .prompt()
+                    .map(|s| crate::parse_date(&s).unwrap())?,
+            ),
+            None => None,
+        };
+        Ok(Answers {
+            name,
+            subname,
+            place,
+            info,
+            stage,
+            status: statuses[status.index],
+            date,
+        })
+    })();
+    match answers {
+        Ok(answers) => Ok(Some(answers)),
+        Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// print the answers and ask if we should save them
+pub(crate) fn confirm(answers: &Answers, question: &str) -> anyhow::Result<bool> {
+    let date = answers.date.map(|d| d.format(&FORMAT).unwrap());
+    let rows = [
+        ("Company", Some(answers.name.clone())),
+        ("Job", Some(answers.subname.clone())),
+        ("Place", Some(answers.place.clone())),
+        ("Info", Some(answers.info.clone())),
+        ("Stage", Some(answers.stage.clone())),
+        ("Status", Some(answers.status.name())),
+        ("Date", date),
+    ];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            println!("{:>10}: {}", label.bold(), value);
+        }
+    }
+    prompt::confirm(question)
+}
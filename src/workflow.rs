@@ -0,0 +1,102 @@
+use crate::{
+    config::{self, ColorName, StatusConfig},
+    types::Status,
+};
+
+/// the configuration of the status if we use it
+fn find(status: Status) -> Option<&'static StatusConfig> {
+    config::get()
+        .workflow
+        .statuses
+        .iter()
+        .find(|c| c.status == status)
+}
+
+/// the statuses we use, in the configured order
+pub(crate) fn statuses() -> Vec<Status> {
+    config::get()
+        .workflow
+        .statuses
+        .iter()
+        .map(|c| c.status)
+        .collect()
+}
+
+/// the configured name of the status
+pub(crate) fn name(status: Status) -> Option<String> {
+    find(status).and_then(|c| c.name.clone())
+}
+
+/// the color of the status
+pub(crate) fn color(status: Status) -> ColorName {
+    if let Some(c) = find(status).and_then(|c| c.color) {
+        return c;
+    }
+    match status {
+        Status::Todo => ColorName::Red,
+        Status::Applied | Status::Pending => ColorName::Yellow,
+        Status::Interviewing => ColorName::Cyan,
+        Status::Offer => ColorName::Magenta,
+        Status::Accepted | Status::Rejected | Status::Declined | Status::Withdrawn => {
+            ColorName::Green
+        }
+        Status::Ghosted => ColorName::Blue,
+    }
+}
+
+/// are we still in the process for jobs with this status
+pub(crate) fn is_open(status: Status) -> bool {
+    find(status).and_then(|c| c.open).unwrap_or(matches!(
+        status,
+        Status::Todo | Status::Applied | Status::Pending | Status::Interviewing | Status::Offer
+    ))
+}
+
+/// the default transitions, the first one is the one enter used to cycle to
+fn default_next(status: Status) -> Vec<Status> {
+    match status {
+        Status::Todo => vec![Status::Pending, Status::Applied, Status::Withdrawn],
+        Status::Applied => vec![
+            Status::Pending,
+            Status::Interviewing,
+            Status::Rejected,
+            Status::Withdrawn,
+            Status::Ghosted,
+        ],
+        Status::Pending => vec![
+            Status::Rejected,
+            Status::Interviewing,
+            Status::Offer,
+            Status::Todo,
+            Status::Withdrawn,
+            Status::Ghosted,
+        ],
+        Status::Interviewing => vec![
+            Status::Pending,
+            Status::Offer,
+            Status::Rejected,
+            Status::Withdrawn,
+            Status::Ghosted,
+        ],
+        Status::Offer => vec![Status::Accepted, Status::Declined, Status::Withdrawn],
+        Status::Accepted => vec![Status::Withdrawn, Status::Todo],
+        Status::Rejected | Status::Declined | Status::Withdrawn => vec![Status::Todo],
+        Status::Ghosted => vec![Status::Todo, Status::Interviewing, Status::Rejected],
+    }
+}
+
+/// the statuses we are allowed to change to from `status`
+pub(crate) fn next(status: Status) -> Vec<Status> {
+    let used = statuses();
+    find(status)
+        .and_then(|c| c.next.clone())
+        .unwrap_or_else(|| default_next(status))
+        .into_iter()
+        .filter(|s| used.contains(s))
+        .collect()
+}
+
+/// can we change from `from` to `to`
+pub(crate) fn allows(from: Status, to: Status) -> bool {
+    from == to || next(from).contains(&to)
+}